@@ -5,12 +5,14 @@ import {
   PublicKey,
   Keypair,
   SystemProgram,
+  SYSVAR_SLOT_HASHES_PUBKEY,
   Transaction,
   Connection,
   sendAndConfirmTransaction,
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
   createAssociatedTokenAccountInstruction,
   createMint,
   getAccount,
@@ -34,6 +36,8 @@ export class FortuneXClient {
   private readonly LOTTERY_POOL_SEED = "lottery_pool";
   private readonly VAULT_AUTHORITY_SEED = "vault_authority";
   private readonly USER_TICKET_SEED = "user_ticket";
+  private readonly TICKET_PAGE_SEED = "ticket_page";
+  private readonly RANGES_PER_PAGE = 200;
 
   constructor(connection?: Connection, wallet?: anchor.Wallet) {
     // Option 1: Use provided connection and wallet
@@ -118,7 +122,8 @@ export class FortuneXClient {
     authority: Keypair,
     platformWallet: PublicKey,
     usdcMint: PublicKey,
-    platformFeePercentage: number = 100
+    platformFeePercentage: number = 100,
+    updateDelay: number = 24 * 60 * 60
   ): Promise<string> {
     const [globalStatePda] = PublicKey.findProgramAddressSync(
       [Buffer.from(this.GLOBAL_STATE_SEED)],
@@ -126,12 +131,13 @@ export class FortuneXClient {
     );
 
     const tx = await this.program.methods
-      .initialize(platformWallet, usdcMint, 50, 50)
+      .initialize(platformWallet, usdcMint, 50, 50, new anchor.BN(updateDelay))
       .accounts({
         globalState: globalStatePda,
         usdcMint: usdcMint,
         authority: authority.publicKey,
         platformWallet: platformWallet,
+        tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       } as any)
       .signers([authority])
@@ -176,7 +182,12 @@ export class FortuneXClient {
   async createLotteryPool(
     creator: Keypair,
     drawInterval: number = 300, // Default 5 minutes (300 seconds)
-    poolId?: number
+    poolId?: number,
+    prizeSchedule: object = { equal: { winners: 1 } }, // Single winner by default
+    recurring: boolean = false, // Reopen the pool for a new round after every draw
+    minTicketsPolicy: object = { rollover: { maxRollovers: 3 } }, // Then refund
+    commissionBps: number = 0, // Creator commission, capped by the global max
+    mint?: PublicKey // Payment mint from the accepted mints, the USDC mint by default, PublicKey.default for native SOL
   ): Promise<{ txSignature: string; poolPda: PublicKey; poolId: number }> {
     const [globalStatePda] = PublicKey.findProgramAddressSync(
       [Buffer.from(this.GLOBAL_STATE_SEED)],
@@ -218,7 +229,9 @@ export class FortuneXClient {
     const globalState = await this.program.account.globalState.fetch(
       globalStatePda
     );
-    const usdcMint = globalState.usdcMint;
+    const usdcMint = mint ?? globalState.usdcMint;
+    // Native SOL pools take no token accounts
+    const native = usdcMint.equals(PublicKey.default);
     // if (!this.creatorTokenAccount) {
     this.creatorTokenAccount = await getAssociatedTokenAddress(
       usdcMint,
@@ -231,17 +244,21 @@ export class FortuneXClient {
         new anchor.BN(1000_000_000),
         new anchor.BN(100),
         new anchor.BN(100),
-        new anchor.BN(drawInterval)
+        new anchor.BN(drawInterval),
+        prizeSchedule,
+        recurring,
+        minTicketsPolicy,
+        commissionBps
       )
       .accounts({
         globalState: globalStatePda,
         lotteryPool: lotteryPoolPda,
-        poolTokenAccount,
+        poolTokenAccount: native ? null : poolTokenAccount,
         vaultAuthority,
-        usdcMint,
-        creatorTokenAccount: this.creatorTokenAccount,
+        mint: native ? null : usdcMint,
+        creatorTokenAccount: native ? null : this.creatorTokenAccount,
         authority: creator.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenProgram: native ? null : TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       } as any)
       .signers([creator])
@@ -283,11 +300,14 @@ export class FortuneXClient {
       this.program.programId
     );
 
+    // Tickets are kept per round of the pool
+    const pool = await this.program.account.lotteryPool.fetch(lotteryPoolPda);
     const [userTicketPda] = PublicKey.findProgramAddressSync(
       [
         Buffer.from(this.USER_TICKET_SEED),
         user.publicKey.toBuffer(),
         new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+        pool.round.toArrayLike(Buffer, "le", 8),
       ],
       this.program.programId
     );
@@ -300,28 +320,49 @@ export class FortuneXClient {
       this.program.programId
     );
 
-    const globalState = await this.program.account.globalState.fetch(
-      globalStatePda
-    );
-    const usdcMint = globalState.usdcMint;
+    // Tickets are paid in the mint the pool was created with, native SOL pools take no token accounts
+    const usdcMint = pool.mint;
+    const native = usdcMint.equals(PublicKey.default);
 
     const userTokenAccount = await getAssociatedTokenAddress(
       usdcMint,
       user.publicKey
     );
 
+    // The purchase is recorded as a range in the current ticket page,
+    // allocate it in the same transaction when the previous page is full
+    const pageIndex = Math.floor(pool.rangeCount.toNumber() / this.RANGES_PER_PAGE);
+    const preInstructions = [];
+    if (pageIndex >= pool.pageCount) {
+      preInstructions.push(
+        await this.program.methods
+          .addTicketPage(new anchor.BN(poolId))
+          .accounts({
+            lotteryPool: lotteryPoolPda,
+            ticketPage: this.ticketPagePda(poolId, pageIndex),
+            payer: user.publicKey,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .instruction()
+      );
+    }
+
     const tx = await this.program.methods
-      .buyTicket(new anchor.BN(poolId), new anchor.BN(quantity))
+      .buyTicket(new anchor.BN(poolId), new anchor.BN(quantity), null)
       .accounts({
         globalState: globalStatePda,
         lotteryPool: lotteryPoolPda,
         userTicket: userTicketPda,
-        userTokenAccount: userTokenAccount,
-        poolTokenAccount: poolTokenAccount,
+        ticketPage: this.ticketPagePda(poolId, pageIndex),
+        userTokenAccount: native ? null : userTokenAccount,
+        poolTokenAccount: native ? null : poolTokenAccount,
+        vaultAuthority: poolTokenAccount,
         user: user.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        mint: native ? null : usdcMint,
+        tokenProgram: native ? null : TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       } as any)
+      .preInstructions(preInstructions)
       .signers([user])
       .rpc();
 
@@ -346,14 +387,6 @@ export class FortuneXClient {
       this.program.programId
     );
 
-    const [drawHistoryPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("draw_history"),
-        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
-      ],
-      this.program.programId
-    );
-
     const [poolTokenAccount] = PublicKey.findProgramAddressSync(
       [
         Buffer.from(this.VAULT_AUTHORITY_SEED),
@@ -370,12 +403,51 @@ export class FortuneXClient {
       this.program.programId
     );
 
-    // Get pool to fetch participants
-    const pool = await this.program.account.lotteryPool.fetch(lotteryPoolPda);
+    // Close sales and commit the draw to a future slot, then wait for it
+    let pool = await this.program.account.lotteryPool.fetch(lotteryPoolPda);
+    if (pool.drawCommitSlot.toNumber() === 0) {
+      const tx = await this.program.methods
+        .commitDraw(new anchor.BN(poolId))
+        .accounts({
+          globalState: globalStatePda,
+          lotteryPool: lotteryPoolPda,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+          crank: crank.publicKey,
+        } as any)
+        .signers([crank])
+        .rpc();
+
+      pool = await this.program.account.lotteryPool.fetch(lotteryPoolPda);
+      if (pool.drawCommitSlot.toNumber() === 0) {
+        // Not enough tickets sold, the program moved the draw time forward
+        return { txSignature: tx, drawHistory: null };
+      }
+    }
+    while (
+      (await this.provider.connection.getSlot()) <= pool.drawCommitSlot.toNumber()
+    ) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+
     const globalState = await this.program.account.globalState.fetch(
       globalStatePda
     );
-    const usdcMint = globalState.usdcMint;
+
+    // The draw is recorded under the next global draw index
+    const [drawHistoryPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("draw_history"),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+        globalState.drawsCount.toArrayLike(Buffer, "le", 8),
+      ],
+      this.program.programId
+    );
+    const usdcMint = pool.mint;
+    const native = usdcMint.equals(PublicKey.default);
+    const [bonusAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("bonus_authority"), usdcMint.toBuffer()],
+      this.program.programId
+    );
     const platformTokenAccount = await getAssociatedTokenAddress(
       usdcMint,
       globalState.platformWallet
@@ -385,17 +457,24 @@ export class FortuneXClient {
       pool.creator
     );
 
-    // Create remaining accounts for all participants' token accounts
-    const remainingAccounts = await Promise.all(
-      pool.ticketsSold.map(async (participant: PublicKey) => {
-        const ata = await getAssociatedTokenAddress(usdcMint, participant);
-        return {
-          pubkey: ata,
-          isSigner: false,
-          isWritable: true,
-        };
-      })
+    // The draw bounty is paid to the crank's ATA, create it on the first draw
+    const crankTokenAccount = await getAssociatedTokenAddress(
+      usdcMint,
+      crank.publicKey
     );
+    const preInstructions = [];
+    try {
+      if (!native) await getAccount(this.provider.connection, crankTokenAccount);
+    } catch {
+      preInstructions.push(
+        createAssociatedTokenAccountInstruction(
+          crank.publicKey,
+          crankTokenAccount,
+          crank.publicKey,
+          usdcMint
+        )
+      );
+    }
 
     const tx = await this.program.methods
       .drawWinner(new anchor.BN(poolId))
@@ -403,27 +482,40 @@ export class FortuneXClient {
         globalState: globalStatePda,
         lotteryPool: lotteryPoolPda,
         drawHistory: drawHistoryPda,
-        poolTokenAccount: poolTokenAccount,
+        poolTokenAccount: native ? null : poolTokenAccount,
         vaultAuthority: vaultAuthority,
-        platformTokenAccount: platformTokenAccount,
-        creatorTokenAccount: creatorTokenAccount,
+        platformTokenAccount: native ? null : platformTokenAccount,
+        platformWallet: native ? globalState.platformWallet : null,
+        bonusPoolTokenAccount: native ? null : bonusAuthority,
+        bonusAuthority,
+        creatorTokenAccount: native ? null : creatorTokenAccount,
+        creator: native ? pool.creator : null,
+        crankTokenAccount: native ? null : crankTokenAccount,
         crank: crank.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        mint: native ? null : usdcMint,
+        tokenProgram: native ? null : TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       } as any)
-      .remainingAccounts(remainingAccounts)
+      .preInstructions(preInstructions)
       .signers([crank])
       .rpc();
 
     console.log("🎉 Draw winner transaction:", tx);
 
+    // Look up the owners of the winning tickets
+    await this.resolveWinners(crank, poolId);
+
     // Fetch draw history
     const drawHistory = await this.program.account.drawHistory.fetch(
       drawHistoryPda
     );
     console.log("🏆 Draw Results:", {
-      winner: drawHistory.winner.toBase58(),
-      winningTicket: drawHistory.winningTicket.toString(),
+      winners: drawHistory.winners.map((w: any) => ({
+        place: w.place + 1,
+        winner: w.winner.toBase58(),
+        winningTicket: w.ticket.toString(),
+        amount: w.amount.toString(),
+      })),
       prizeAmount: drawHistory.prizeAmount.toString(),
       drawTime: new Date(drawHistory.drawTimestamp.toNumber() * 1000),
     });
@@ -434,6 +526,215 @@ export class FortuneXClient {
     };
   }
 
+  private ticketPagePda(poolId: number, index: number): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(this.TICKET_PAGE_SEED),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+        new anchor.BN(index).toArrayLike(Buffer, "le", 4),
+      ],
+      this.program.programId
+    )[0];
+  }
+
+  // Resolve the owners of the winning tickets of a drawn round from the ticket pages
+  async resolveWinners(payer: Keypair, poolId: number): Promise<string> {
+    const [lotteryPoolPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(this.LOTTERY_POOL_SEED),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+      ],
+      this.program.programId
+    );
+
+    const [globalStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(this.GLOBAL_STATE_SEED)],
+      this.program.programId
+    );
+
+    const pool = await this.program.account.lotteryPool.fetch(lotteryPoolPda);
+    const [drawHistoryPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("draw_history"),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+        pool.drawIndex.toArrayLike(Buffer, "le", 8),
+      ],
+      this.program.programId
+    );
+
+    // Every page holding ranges of the round is needed, reissued tickets are
+    // recorded in later ranges that override the range they were first bought in.
+    // Ranges past the pool's range count are left over from previous rounds.
+    const pageCount = Math.ceil(pool.rangeCount.toNumber() / this.RANGES_PER_PAGE);
+    const pageIndexes = [...Array(pageCount).keys()];
+
+    const tx = await this.program.methods
+      .resolveWinners(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: lotteryPoolPda,
+        drawHistory: drawHistoryPda,
+        payer: payer.publicKey,
+      } as any)
+      .remainingAccounts(
+        pageIndexes.map((index) => ({
+          pubkey: this.ticketPagePda(poolId, index),
+          isWritable: false,
+          isSigner: false,
+        }))
+      )
+      .signers([payer])
+      .rpc();
+
+    console.log(`🔎 Winners of pool ${poolId} resolved: ${tx}`);
+    return tx;
+  }
+
+  // Refund every participant of a pool that did not sell enough tickets
+  async refundAll(payer: Keypair, poolId: number): Promise<string | null> {
+    const [globalStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(this.GLOBAL_STATE_SEED)],
+      this.program.programId
+    );
+
+    const [lotteryPoolPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(this.LOTTERY_POOL_SEED),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+      ],
+      this.program.programId
+    );
+
+    const [vaultAuthority] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(this.VAULT_AUTHORITY_SEED),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+      ],
+      this.program.programId
+    );
+
+    const pool = await this.program.account.lotteryPool.fetch(lotteryPoolPda);
+
+    // Ticket accounts of the current round that still hold tickets
+    const userTickets = (
+      await this.program.account.userTicket.all([
+        { memcmp: { offset: 8 + 32, bytes: lotteryPoolPda.toBase58() } },
+      ])
+    ).filter(
+      ({ account }) =>
+        account.round.eq(pool.round) && account.tickets.length > 0
+    );
+    if (userTickets.length === 0) return null;
+
+    // Native SOL pools refund straight to the participants' wallets
+    const native = pool.mint.equals(PublicKey.default);
+    const remainingAccounts = [];
+    for (const { publicKey, account } of userTickets) {
+      const userTokenAccount = native
+        ? account.user
+        : await getAssociatedTokenAddress(pool.mint, account.user);
+      remainingAccounts.push(
+        { pubkey: publicKey, isWritable: true, isSigner: false },
+        { pubkey: userTokenAccount, isWritable: true, isSigner: false }
+      );
+    }
+
+    const tx = await this.program.methods
+      .refundAll(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: lotteryPoolPda,
+        poolTokenAccount: native ? null : vaultAuthority,
+        vaultAuthority,
+        payer: payer.publicKey,
+        mint: native ? null : pool.mint,
+        tokenProgram: native ? null : TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(remainingAccounts)
+      .signers([payer])
+      .rpc();
+
+    console.log(
+      `💸 Refunded ${userTickets.length} participants of pool ${poolId}: ${tx}`
+    );
+    return tx;
+  }
+
+  async claimPrize(
+    payer: Keypair,
+    poolId: number,
+    drawIndex: number, // Global draw index of the draw, see DrawHistory.drawIndex
+    place: number = 0
+  ): Promise<string> {
+    const [globalStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(this.GLOBAL_STATE_SEED)],
+      this.program.programId
+    );
+
+    const [lotteryPoolPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(this.LOTTERY_POOL_SEED),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+      ],
+      this.program.programId
+    );
+
+    const [drawHistoryPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("draw_history"),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+        new anchor.BN(drawIndex).toArrayLike(Buffer, "le", 8),
+      ],
+      this.program.programId
+    );
+
+    const [vaultAuthority] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(this.VAULT_AUTHORITY_SEED),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+      ],
+      this.program.programId
+    );
+
+    const pool = await this.program.account.lotteryPool.fetch(lotteryPoolPda);
+    const drawHistory = await this.program.account.drawHistory.fetch(
+      drawHistoryPda
+    );
+    const winner = drawHistory.winners[place].winner;
+    const winnerTokenAccount = await getAssociatedTokenAddress(
+      pool.mint,
+      winner
+    );
+
+    // The winner's ATA is created by the program if it does not exist yet,
+    // native SOL prizes are paid straight to the winner's wallet
+    const native = pool.mint.equals(PublicKey.default);
+    const tx = await this.program.methods
+      .claimPrize(new anchor.BN(poolId), new anchor.BN(drawIndex), place)
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: lotteryPoolPda,
+        drawHistory: drawHistoryPda,
+        poolTokenAccount: native ? null : vaultAuthority,
+        vaultAuthority,
+        winner,
+        winnerTokenAccount: native ? null : winnerTokenAccount,
+        mint: native ? null : pool.mint,
+        payer: payer.publicKey,
+        tokenProgram: native ? null : TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([payer])
+      .rpc();
+
+    console.log(
+      `💸 Prize of pool ${poolId} round ${drawHistory.round} place ${place + 1} claimed for ${winner}: ${tx}`
+    );
+    return tx;
+  }
+
   async getPoolInfo(poolId: number): Promise<any> {
     const [lotteryPoolPda] = PublicKey.findProgramAddressSync(
       [
@@ -451,14 +752,15 @@ export class FortuneXClient {
     return {
       poolId: pool.poolId.toString(),
       drawInterval: pool.drawInterval.toNumber(),
-      ticketsSold: pool.ticketsSold,
-      participants: pool.ticketsSold.map((p: PublicKey) => p.toBase58()),
+      ticketsSold: pool.ticketsSold.toNumber(),
       createdAt: new Date(pool.createdAt.toNumber() * 1000),
       expiresAt: new Date(expiryTime * 1000),
       isExpired,
       timeRemaining: Math.max(0, expiryTime - currentTime),
       status: pool.status,
       maxTickets: pool.maxTickets,
+      round: pool.round,
+      recurring: pool.recurring,
     };
   }
 
@@ -691,7 +993,13 @@ async function main() {
       );
 
       // Create first lottery pool
-      const poolResult = await client.createLotteryPool(creator, 86_400); // 300 seconds = 5 minutes
+      const poolResult = await client.createLotteryPool(
+        creator,
+        86_400,
+        undefined,
+        undefined,
+        true
+      ); // 300 seconds = 5 minutes
       console.log("🎉 First pool created!");
       console.log("Pool ID:", poolResult.poolId);
       console.log("Pool PDA:", poolResult.poolPda.toBase58());
@@ -326,7 +326,7 @@ class LotteryBotManager {
 
         // Get fresh pool info to check current state
         const poolInfo = await this.client.getPoolInfo(poolId);
-        const ticketsSold = poolInfo.ticketsSold?.length || 0;
+        const ticketsSold = poolInfo.ticketsSold || 0;
         const maxTickets = pool.maxTickets.toNumber();
         const availableTickets = maxTickets - ticketsSold;
 
@@ -377,7 +377,7 @@ class LotteryBotManager {
 
         // Get fresh pool state before each purchase
         const currentPoolInfo = await this.client.getPoolInfo(poolId);
-        const currentTicketsSold = currentPoolInfo.ticketsSold?.length || 0;
+        const currentTicketsSold = currentPoolInfo.ticketsSold || 0;
         const maxTickets = await this.getPoolMaxTickets(poolId);
         const currentAvailableTickets = maxTickets - currentTicketsSold;
 
@@ -438,7 +438,7 @@ class LotteryBotManager {
 
         // Check if pool is now full after this purchase
         const updatedPoolInfo = await this.client.getPoolInfo(poolId);
-        const updatedTicketsSold = updatedPoolInfo.ticketsSold?.length || 0;
+        const updatedTicketsSold = updatedPoolInfo.ticketsSold || 0;
         const updatedAvailableTickets = maxTickets - updatedTicketsSold;
 
         console.log(
@@ -533,17 +533,12 @@ async function main() {
     cron.schedule(CONFIG.DRAW_CHECK_INTERVAL, async () => {
       console.log("🔁 Cron Job: Checking pools for drawing...");
       try {
+        // Draws are permissionless, any wallet can run the crank and earn the bounty
         let globalState = await client.program.account.globalState.all();
-        if (
-          globalState?.[0]?.account?.creatorsWhitelist?.some((key) =>
-            key.equals(wallet.publicKey)
-          )
-        ) {
+        if (globalState.length > 0) {
           await drawPools(client, wallet);
         } else {
-          console.log(
-            "🚫 Unauthorized to run crank or contract may not be initialized yet"
-          );
+          console.log("🚫 Contract may not be initialized yet");
         }
       } catch (err: any) {
         console.error("❌ Error in draw pools cron job:", err.message);
@@ -580,6 +575,26 @@ async function drawPools(client: FortuneXClient, wallet: Keypair) {
     const poolId = pool.poolId.toNumber();
     const drawTime = pool.drawTime.toNumber();
 
+    // Finish draws whose winners were picked but not resolved yet
+    if (pool.status.settling) {
+      try {
+        await client.resolveWinners(wallet, poolId);
+      } catch (err: any) {
+        console.log(`❌ Error when resolving pool ${poolId}:`, err.message);
+      }
+      continue;
+    }
+
+    // Pools that did not sell enough tickets are refunded instead of drawn
+    if (pool.status.refunding) {
+      try {
+        await client.refundAll(wallet, poolId);
+      } catch (err: any) {
+        console.log(`❌ Error when refunding pool ${poolId}:`, err.message);
+      }
+      continue;
+    }
+
     // Skip if not ready to draw or already completed
     if (drawTime > now || pool.status.completed) continue;
 
@@ -589,19 +604,39 @@ async function drawPools(client: FortuneXClient, wallet: Keypair) {
       const drawResult = await client.drawWinner(wallet, poolId);
       let info = await client.getPoolInfo(poolId);
 
-      if (info.status.completed) {
+      if (drawResult.drawHistory) {
         console.log("🏆 Draw completed!");
-        console.log(`   Winner: ${drawResult.drawHistory.winner.toBase58()}`);
+        for (const w of drawResult.drawHistory.winners) {
+          console.log(`   Place ${w.place + 1}: ${w.winner.toBase58()}`);
+        }
         console.log(
           `   Prize: ${drawResult.drawHistory.prizeAmount.toString()}`
         );
-        console.log("🆕 Initializing new pool...");
 
-        const newPool = await client.createLotteryPool(wallet, POOL_INTERVAL);
-        console.log(`✅ New pool created with ID: ${newPool.poolId}`);
+        // Prizes are pulled from the vault, claim on each winner's behalf
+        const drawIndex = drawResult.drawHistory.drawIndex.toNumber();
+        for (const w of drawResult.drawHistory.winners) {
+          await client.claimPrize(wallet, poolId, drawIndex, w.place);
+        }
+
+        // Recurring pools reopen by themselves, one-shot pools are replaced
+        if (info.recurring) {
+          console.log(`🔁 Pool ${poolId} reopened for round ${info.round.toString()}`);
+        } else {
+          console.log("🆕 Initializing new pool...");
+
+          const newPool = await client.createLotteryPool(
+            wallet,
+            POOL_INTERVAL,
+            undefined,
+            undefined,
+            true
+          );
+          console.log(`✅ New pool created with ID: ${newPool.poolId}`);
+        }
       } else {
         console.log(
-          `⏰ Draw time of Pool ${poolId} extended due to insufficient sold tickets`
+          `⏰ Pool ${poolId} did not sell enough tickets, draw extended or refunding`
         );
       }
     } catch (err: any) {
@@ -11,13 +11,48 @@ pub const USER_TICKET_SEED: &[u8] = b"user_ticket";
 pub const POOL_VAULT_SEED: &[u8] = b"pool_vault";
 pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault_authority";
 pub const DRAW_HISTORY_SEED: &[u8] = b"draw_history";
+pub const RECENT_DRAWS_SEED: &[u8] = b"recent_draws";
+pub const TICKET_PAGE_SEED: &[u8] = b"ticket_page";
+pub const BONUS_ROUND_SEED: &[u8] = b"bonus_round";
+pub const BONUS_HISTORY_SEED: &[u8] = b"bonus_history";
+
+// Ticket ledger configuration
+pub const RANGES_PER_PAGE: usize = 200; // Keeps a ticket page under the 10KB account creation limit
 
 // Platform fee configuration
 pub const DEFAULT_PLATFORM_FEE_BPS: u16 = 100; // 1% (100 basis points)
 pub const MAX_PLATFORM_FEE_BPS: u16 = 1000;    // 10% maximum allowed
 pub const MAX_BONUS_POOL_FEE_BPS: u16 = 1000;    // 10% maximum allowed
+pub const MAX_CRANK_BOUNTY_BPS: u16 = 100;       // Draw bounty never exceeds 1% of the prize pool
+
+// Creator commission configuration
+pub const DEFAULT_MAX_CREATOR_COMMISSION_BPS: u16 = 500; // 5% default cap on pool commissions
+pub const MAX_CREATOR_COMMISSION_BPS: u16 = 2000;        // 20% maximum allowed cap
+
+// Parameter update configuration
+pub const MAX_UPDATE_DELAY: i64 = 30 * 24 * 60 * 60; // Queued updates wait at most 30 days
+
+// Pause flags, one bit per operation of GlobalState.paused. Refunds are never paused.
+pub const PAUSE_BUY: u8 = 1 << 0;    // buy_ticket
+pub const PAUSE_CANCEL: u8 = 1 << 1; // cancel_ticket
+pub const PAUSE_DRAW: u8 = 1 << 2;   // Committing, requesting, completing and resolving draws
+pub const PAUSE_CLAIM: u8 = 1 << 3;  // claim_prize and claim_bonus
+pub const PAUSE_ALL: u8 = PAUSE_BUY | PAUSE_CANCEL | PAUSE_DRAW | PAUSE_CLAIM;
 
 // Draw configuration
 pub const DEFAULT_DRAW_INTERVAL: i64 = 24 * 60 * 60; // 24 hours in seconds
+#[cfg(not(feature = "localnet"))]
 pub const MIN_DRAW_INTERVAL: i64 = 60 * 60;          // 1 hour minimum
-pub const MAX_DRAW_INTERVAL: i64 = 7 * 24 * 60 * 60; // 7 days maximum
\ No newline at end of file
+#[cfg(feature = "localnet")]
+pub const MIN_DRAW_INTERVAL: i64 = 1; // Lets the local test suite draw pools within seconds
+pub const MAX_DRAW_INTERVAL: i64 = 7 * 24 * 60 * 60; // 7 days maximum
+
+// Slot commitment configuration
+pub const DRAW_COMMIT_SLOT_DELAY: u64 = 10; // Draw uses the hash of a slot ~4 seconds after commit
+pub const DRAW_RECOMMIT_PENALTY_SLOTS: u64 = 9_000; // Expired commitments are replaced ~1 hour after their slot
+
+// VRF configuration
+pub const VRF_REQUEST_TIMEOUT: i64 = 60 * 60; // Unanswered VRF requests can be replaced after 1 hour
+
+// Commit-reveal configuration
+pub const REVEAL_WINDOW: i64 = 60 * 60; // Reveals open 1 hour before draw time (capped at half the interval)
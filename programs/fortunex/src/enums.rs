@@ -4,5 +4,38 @@ use anchor_lang::prelude::*;
 pub enum PoolStatus {
     Active,    // Pool is accepting tickets
     PoolFull,  // Pool filled
+    Drawing,   // Sales closed, waiting for randomness
+    Settling,  // Winning tickets drawn, waiting for their owners to be resolved
     Completed, // Draw completed
+    Refunding, // Minimum tickets not sold, tickets are refunded in full
+    Paused,    // Paused by the authority or guardian, the previous status is restored when unpaused
+}
+
+// What happens when the draw time passes without min_tickets sold
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum MinTicketsPolicy {
+    Rollover { max_rollovers: u8 }, // Move the draw time forward up to max_rollovers times, then refund
+    Refund,                         // Refund every ticket at the first missed draw
+}
+
+// Reward paid out of the prize pool to whoever completes a draw
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum CrankBounty {
+    Fixed { amount: u64 },             // Fixed amount per draw
+    Bps { bps: u16, max_amount: u64 }, // Share of the prize pool, capped at max_amount
+}
+
+// Where the random seed of a draw came from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum RandomnessSource {
+    Unknown,                             // Not recorded, draws before draw history version 1
+    SlotHashes { slot: u64 },            // Revealed entropy mixed with the hash of this slot
+    Vrf { account: Pubkey, round: u64 }, // VRF result account and the round that settled the draw
+}
+
+// How the prize left after fees is split between winners
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum PrizeSchedule {
+    Tiered { shares_bps: Vec<u16> }, // Share of each place in basis points, first place first
+    Equal { winners: u8 },           // Number of winners sharing the prize equally
 }
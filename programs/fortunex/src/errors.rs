@@ -40,6 +40,62 @@ pub enum FortuneXError {
     InvalidPlatformFee,
     #[msg("Invalid bonus pool fee")]
     InvalidBonusPoolFee,
+    #[msg("Invalid crank bounty")]
+    InvalidCrankBounty,
+    #[msg("Invalid creator commission")]
+    InvalidCommission,
+    #[msg("Creator commission can only be lowered")]
+    CommissionIncrease,
+    #[msg("Fees and commission exceed the prize pool")]
+    InvalidFeeTotal,
+    #[msg("Invalid pending authority")]
+    InvalidPendingAuthority,
+    #[msg("No authority transfer pending")]
+    NoPendingAuthority,
+    #[msg("Invalid update delay")]
+    InvalidUpdateDelay,
+    #[msg("No parameter update queued")]
+    NoPendingUpdate,
+    #[msg("Queued parameter update is not effective yet")]
+    UpdateNotReady,
+    #[msg("Mint cannot change while pools or bonus prizes hold funds")]
+    MintInUse,
+    #[msg("Mint is not accepted for new pools")]
+    MintNotAccepted,
+    #[msg("Mint is already accepted")]
+    MintAlreadyAccepted,
+    #[msg("Accepted mints list is full")]
+    MintAllowlistFull,
+    #[msg("Token accounts required by the pool mint were not provided")]
+    MissingTokenAccounts,
+    #[msg("Operation is paused")]
+    OperationPaused,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    #[msg("Pool is paused")]
+    PoolPaused,
+    #[msg("Pool is not paused")]
+    PoolNotPaused,
+    #[msg("Draw history must be migrated to the current version first")]
+    DrawHistoryOutdated,
+    #[msg("Draw history does not belong to the pool")]
+    InvalidDrawHistory,
+    #[msg("User ticket does not belong to the current round of the pool or is passed twice")]
+    InvalidUserTicket,
+    #[msg("Pool vault holds no surplus")]
+    NoSurplus,
+    #[msg("Pool round is not drawn or refunded yet")]
+    PoolNotSettled,
+    #[msg("Pool still holds funds or unclaimed prizes")]
+    PoolNotEmpty,
+    #[msg("Every ticket page of the pool must be closed with it")]
+    TicketPagesNotClosed,
+    #[msg("Draw history still holds unclaimed prizes")]
+    PrizesNotClaimed,
+    #[msg("Pools are still taking or refunding tickets")]
+    PoolsStillActive,
+    #[msg("Pool is not recurring")]
+    PoolNotRecurring,
     #[msg("Creator is already whitelisted")]
     CreatorAlreadyWhitelisted,
     #[msg("Whitelist is full")]
@@ -69,4 +125,109 @@ pub enum FortuneXError {
 
     #[msg("Invalid ticket quantity")]
     InvalidTicketQuantity,
+
+    #[msg("VRF oracle program is not configured")]
+    VrfNotConfigured,
+
+    #[msg("Invalid VRF account")]
+    InvalidVrfAccount,
+
+    #[msg("VRF result for the requested round is not available yet")]
+    VrfRoundNotFulfilled,
+
+    #[msg("VRF round after the request was missed, request the draw again")]
+    VrfRoundMissed,
+
+    #[msg("No draw has been requested for this pool")]
+    DrawNotRequested,
+
+    #[msg("Commitments are closed once the reveal window opens")]
+    CommitWindowClosed,
+
+    #[msg("Reveal window is not open")]
+    RevealWindowNotOpen,
+
+    #[msg("Too many entropy commitments")]
+    TooManyCommitments,
+
+    #[msg("Revealed value does not match any pending commitment")]
+    InvalidReveal,
+
+    #[msg("Invalid SlotHashes sysvar data")]
+    InvalidSlotHashes,
+
+    #[msg("Draw has not been committed to a slot")]
+    DrawNotCommitted,
+
+    #[msg("Committed draw slot has not been reached yet")]
+    DrawCommitSlotNotReached,
+
+    #[msg("Committed draw slot is no longer in SlotHashes, commit the draw again")]
+    DrawCommitSlotExpired,
+
+    #[msg("Draw is already committed")]
+    DrawAlreadyCommitted,
+
+    #[msg("Expired draw commitment cannot be replaced before the penalty window passes")]
+    DrawRecommitTooEarly,
+
+    #[msg("Prize already claimed")]
+    PrizeAlreadyClaimed,
+
+    #[msg("Account is not the draw winner")]
+    InvalidWinner,
+
+    #[msg("Invalid prize schedule")]
+    InvalidPrizeSchedule,
+
+    #[msg("Not enough tickets for every prize place")]
+    NotEnoughTickets,
+
+    #[msg("Invalid prize place")]
+    InvalidPrizePlace,
+
+    #[msg("Pool is refunding tickets")]
+    PoolRefunding,
+
+    #[msg("Pool is not refunding tickets")]
+    PoolNotRefunding,
+
+    #[msg("Invalid refund accounts")]
+    InvalidRefundAccounts,
+
+    #[msg("Ticket page does not belong to the pool")]
+    InvalidTicketPage,
+
+    #[msg("Ticket page missing for a ticket")]
+    TicketPageMissing,
+
+    #[msg("Pool already has enough ticket pages")]
+    TooManyTicketPages,
+
+    #[msg("Too many cancelled tickets in the round")]
+    TooManyCancelledTickets,
+
+    #[msg("Winners are not drawn yet")]
+    WinnersNotDrawn,
+
+    #[msg("Too many ticket ranges for the user")]
+    TooManyTicketRanges,
+
+    #[msg("Bonus round is closed for new entries")]
+    BonusRoundClosed,
+
+    #[msg("Bonus round has not ended yet")]
+    BonusRoundNotEnded,
+
+    #[msg("User holds no drawn ticket bought in the bonus mint during the bonus round")]
+    NotEligibleForBonus,
+
+    #[msg("User already entered the bonus round")]
+    AlreadyEnteredBonus,
+
+    #[msg("Bonus round is full")]
+    BonusRoundFull,
+
+    #[msg("Bonus round already drawn")]
+    BonusAlreadyDrawn,
 }
@@ -0,0 +1,363 @@
+use crate::enums::CrankBounty;
+use crate::{BonusWinner, PendingGlobalUpdate, PrizeWinner};
+use anchor_lang::prelude::*;
+
+// Emitted when a creator opens a new lottery pool
+#[event]
+pub struct PoolCreated {
+    pub pool_id: u64,
+    pub creator: Pubkey,
+    pub mint: Pubkey, // Default pubkey for native SOL pools
+    pub ticket_price: u64,
+    pub min_tickets: u64,
+    pub max_tickets: u64,
+    pub draw_time: i64,
+    pub recurring: bool,
+    pub commission_bps: u16,
+    pub platform_fee_bps: u16, // Fees snapshotted for the pool
+    pub bonus_pool_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+// Emitted for every ticket purchase
+#[event]
+pub struct TicketsBought {
+    pub pool_id: u64,
+    pub round: u64,
+    pub user: Pubkey,
+    pub quantity: u64,
+    pub reissued_tickets: Vec<u64>, // Cancelled ticket numbers handed out again
+    pub start_ticket: u64,          // First ticket of the new range
+    pub count: u64,                 // Tickets in the new range (0 = none)
+    pub amount: u64,                // What the pool vault received
+    pub prize_pool: u64,            // Prize pool after the purchase
+    pub tickets_sold: u64,
+    pub timestamp: i64,
+}
+
+// Emitted when a user cancels one of their tickets
+#[event]
+pub struct TicketCancelled {
+    pub pool_id: u64,
+    pub round: u64,
+    pub user: Pubkey,
+    pub ticket_number: u64,
+    pub amount_paid: u64,      // What the ticket was bought for
+    pub refund_amount: u64,    // Paid back to the user
+    pub cancellation_fee: u64, // Paid to the platform
+    pub timestamp: i64,
+}
+
+// Emitted when tickets of a pool that did not sell enough are refunded in full
+#[event]
+pub struct TicketsRefunded {
+    pub pool_id: u64,
+    pub round: u64,
+    pub user: Pubkey,
+    pub tickets: u64,
+    pub amount: u64, // Paid back to the user
+    pub timestamp: i64,
+}
+
+// Emitted when a draw picks its winning tickets and pays out the fees
+#[event]
+pub struct DrawCompleted {
+    pub pool_id: u64,
+    pub round: u64,
+    pub draw_index: u64, // Global index the draw history is stored under
+    pub total_tickets: u64,
+    pub prize_pool: u64, // Prize pool before fees
+    pub platform_fee: u64,
+    pub bonus_pool_fee: u64,
+    pub commission: u64, // Paid to the pool creator
+    pub crank: Pubkey,
+    pub crank_bounty: u64,
+    pub winner_prize: u64, // Split between the winners
+    pub random_seed: [u8; 32],
+    pub winners: Vec<PrizeWinner>, // Owners are set later, see WinnersResolved
+    pub timestamp: i64,
+}
+
+// Emitted when resolve_winners looks up the owners of the winning tickets
+#[event]
+pub struct WinnersResolved {
+    pub pool_id: u64,
+    pub round: u64,
+    pub draw_index: u64,
+    pub winners: Vec<PrizeWinner>,
+    pub timestamp: i64,
+}
+
+// Emitted when a winner claims the prize of a pool draw
+#[event]
+pub struct PrizeClaimed {
+    pub pool_id: u64,
+    pub round: u64,
+    pub draw_index: u64,
+    pub place: u8, // 0 = first place
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// Emitted when a winner claims the prize of a bonus round
+#[event]
+pub struct BonusClaimed {
+    pub round_id: u64,
+    pub mint: Pubkey, // Default pubkey for native SOL
+    pub place: u8,    // 0 = first place
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// Emitted when a creator is added to or removed from the whitelist
+#[event]
+pub struct WhitelistUpdated {
+    pub creator: Pubkey,
+    pub is_add: bool,
+}
+
+// Emitted when the authority updates global state
+#[event]
+pub struct GlobalStateUpdated {
+    pub usdc_mint: Pubkey,
+    pub vrf_program: Pubkey,
+    pub vrf_account: Pubkey,
+    pub guardian: Pubkey,
+    pub queued_update: Option<PendingGlobalUpdate>, // Changes waiting for the update delay
+    pub timestamp: i64,
+}
+
+// Emitted when a queued parameter update is applied
+#[event]
+pub struct PendingUpdateExecuted {
+    pub platform_wallet: Pubkey,
+    pub platform_fee_bps: u16,
+    pub bonus_pool_fee_bps: u16,
+    pub crank_bounty: CrankBounty,
+    pub max_creator_commission_bps: u16,
+    pub update_delay: i64,
+    pub timestamp: i64,
+}
+
+// Emitted when a draw is committed to a future slot hash
+#[event]
+pub struct DrawCommitted {
+    pub pool_id: u64,
+    pub round: u64,
+    pub commit_slot: u64, // Slot whose hash seeds the draw
+    pub timestamp: i64,
+}
+
+// Emitted when a draw waits on the next round of a VRF account
+#[event]
+pub struct DrawRequested {
+    pub pool_id: u64,
+    pub round: u64,
+    pub requester: Pubkey,
+    pub vrf_account: Pubkey,
+    pub vrf_round: u64, // Round published at request time, the draw uses the next one
+    pub timestamp: i64,
+}
+
+// Emitted when the draw time passes without enough tickets and the draw is moved forward
+#[event]
+pub struct DrawRolledOver {
+    pub pool_id: u64,
+    pub round: u64,
+    pub rollovers: u8,
+    pub draw_time: i64, // New draw time
+    pub timestamp: i64,
+}
+
+// Emitted when the draw time passes without enough tickets and the round is refunded
+#[event]
+pub struct PoolRefunding {
+    pub pool_id: u64,
+    pub round: u64,
+    pub tickets_sold: u64, // Tickets left to refund
+    pub timestamp: i64,
+}
+
+// Emitted when a buyer reveals the entropy they committed to at purchase
+#[event]
+pub struct EntropyRevealed {
+    pub pool_id: u64,
+    pub round: u64,
+    pub user: Pubkey,
+    pub value: [u8; 32],
+    pub reveals: u64, // Reveals of the round so far
+    pub timestamp: i64,
+}
+
+// Emitted when the paused operations change program wide
+#[event]
+pub struct PauseUpdated {
+    pub paused: u8, // Bitmask of the PAUSE_* flags
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+// Emitted when a pool is paused or unpaused
+#[event]
+pub struct PoolPauseUpdated {
+    pub pool_id: u64,
+    pub paused: bool,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+// Emitted when the authority proposes its successor
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+// Emitted when the proposed authority accepts
+#[event]
+pub struct AuthorityAccepted {
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+// Emitted when a proposed authority transfer is withdrawn
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey, // The proposal that was cancelled
+    pub timestamp: i64,
+}
+
+// Emitted when a creator lowers the commission of their pool
+#[event]
+pub struct PoolCommissionUpdated {
+    pub pool_id: u64,
+    pub commission_bps: u16,
+    pub timestamp: i64,
+}
+
+// Emitted when a creator stops a recurring pool after its current round
+#[event]
+pub struct PoolStopped {
+    pub pool_id: u64,
+    pub round: u64, // Last round of the pool
+    pub timestamp: i64,
+}
+
+// Emitted when a ticket page is added to the ledger of a pool
+#[event]
+pub struct TicketPageAdded {
+    pub pool_id: u64,
+    pub page_index: u32,
+    pub payer: Pubkey,
+}
+
+// Emitted when a bonus round starts taking entries
+#[event]
+pub struct BonusRoundStarted {
+    pub round_id: u64,
+    pub mint: Pubkey, // Default pubkey for native SOL
+    pub prize_shares_bps: Vec<u16>,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+// Emitted when a participant enters a bonus round
+#[event]
+pub struct BonusRoundEntered {
+    pub round_id: u64,
+    pub user: Pubkey,
+    pub participants: u64, // Participants entered so far
+    pub timestamp: i64,
+}
+
+// Emitted when a bonus draw is committed to a future slot hash
+#[event]
+pub struct BonusDrawCommitted {
+    pub round_id: u64,
+    pub commit_slot: u64,
+    pub timestamp: i64,
+}
+
+// Emitted when a bonus draw picks its winners
+#[event]
+pub struct BonusDrawCompleted {
+    pub round_id: u64,
+    pub mint: Pubkey,
+    pub total_participants: u64,
+    pub prize_amount: u64, // Reserved for the winners until claimed
+    pub random_seed: [u8; 32],
+    pub winners: Vec<BonusWinner>,
+    pub timestamp: i64,
+}
+
+// Emitted when a mint is added to or removed from the accepted mints
+#[event]
+pub struct MintAllowlistUpdated {
+    pub mint: Pubkey, // Default pubkey for native SOL
+    pub is_add: bool,
+}
+
+// Emitted when funds donated to a pool vault are swept to the platform
+#[event]
+pub struct SurplusSwept {
+    pub pool_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// Emitted when a finished pool, its vault and ticket pages are closed
+#[event]
+pub struct PoolClosed {
+    pub pool_id: u64,
+    pub creator: Pubkey, // Received the rent of the pool and vault
+    pub ticket_pages: u32,
+    pub timestamp: i64,
+}
+
+// Emitted when a user closes their tickets of a settled round
+#[event]
+pub struct UserTicketClosed {
+    pub pool_id: u64,
+    pub round: u64,
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
+
+// Emitted when a fully claimed draw history is closed
+#[event]
+pub struct DrawHistoryClosed {
+    pub pool_id: u64,
+    pub round: u64,
+    pub draw_index: u64,
+    pub payer: Pubkey, // Received the rent
+    pub timestamp: i64,
+}
+
+// Emitted when the recent draws record is closed
+#[event]
+pub struct RecentDrawsClosed {
+    pub payer: Pubkey, // Received the rent
+    pub timestamp: i64,
+}
+
+// Emitted when a draw history of the first release is moved to the current layout
+#[event]
+pub struct DrawHistoryMigrated {
+    pub pool_id: u64,
+    pub draw_index: u64, // Index the record is stored under now
+    pub timestamp: i64,
+}
+
+// Emitted when the bonus pool of the first release is moved to the default mint's one
+#[event]
+pub struct BonusPoolMigrated {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
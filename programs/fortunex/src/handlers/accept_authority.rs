@@ -0,0 +1,25 @@
+use crate::instructions::AcceptAuthority;
+use crate::AuthorityAccepted;
+use anchor_lang::prelude::*;
+
+pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let previous_authority = global_state.authority;
+
+    global_state.authority = ctx.accounts.new_authority.key();
+    global_state.pending_authority = Pubkey::default();
+
+    msg!(
+        "Authority transferred from {} to {}",
+        previous_authority,
+        global_state.authority
+    );
+
+    emit!(AuthorityAccepted {
+        previous_authority,
+        authority: global_state.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
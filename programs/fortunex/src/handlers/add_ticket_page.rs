@@ -0,0 +1,46 @@
+use crate::enums::PoolStatus;
+use crate::instructions::AddTicketPage;
+use crate::{FortuneXError, LotteryPool, TicketPageAdded, RANGES_PER_PAGE};
+use anchor_lang::prelude::*;
+
+pub fn add_ticket_page(ctx: Context<AddTicketPage>, pool_id: u64) -> Result<()> {
+    let lottery_pool = &mut ctx.accounts.lottery_pool;
+
+    // Validate pool can still sell tickets
+    require!(
+        lottery_pool.status != PoolStatus::Completed,
+        FortuneXError::PoolDrawCompleted
+    );
+
+    // Validate the ledger does not already hold a range for every ticket number a round
+    // can issue, at most max_tickets live tickets plus the cancelled ones
+    let max_ranges = lottery_pool.max_tickets + LotteryPool::MAX_CANCELLED_TICKETS as u64;
+    require!(
+        (lottery_pool.page_count as u64) * (RANGES_PER_PAGE as u64) < max_ranges,
+        FortuneXError::TooManyTicketPages
+    );
+
+    let mut ticket_page = ctx.accounts.ticket_page.load_init()?;
+    ticket_page.pool = lottery_pool.key();
+    ticket_page.page_index = lottery_pool.page_count;
+    ticket_page.payer = ctx.accounts.payer.key();
+
+    lottery_pool.page_count = lottery_pool
+        .page_count
+        .checked_add(1)
+        .ok_or(FortuneXError::Overflow)?;
+
+    msg!(
+        "Added ticket page {} to pool {}",
+        ticket_page.page_index,
+        pool_id
+    );
+
+    emit!(TicketPageAdded {
+        pool_id,
+        page_index: ticket_page.page_index,
+        payer: ticket_page.payer,
+    });
+
+    Ok(())
+}
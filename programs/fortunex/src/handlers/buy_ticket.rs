@@ -1,16 +1,31 @@
 use crate::enums::PoolStatus;
+use crate::handlers::{optional_info, Vault};
 use crate::instructions::BuyTicket;
 use crate::FortuneXError;
-use crate::TicketDetails;
+use crate::{
+    EntropyCommitment, LotteryPool, TicketRange, TicketsBought, UserTicket, PAUSE_BUY,
+    RANGES_PER_PAGE,
+};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer};
 
-pub fn buy_ticket(ctx: Context<BuyTicket>, pool_id: u64, quantity: u64) -> Result<()> {
+pub fn buy_ticket(
+    ctx: Context<BuyTicket>,
+    pool_id: u64,
+    quantity: u64,
+    commitment: Option<[u8; 32]>,
+) -> Result<()> {
     let lottery_pool = &mut ctx.accounts.lottery_pool;
     let user_ticket = &mut ctx.accounts.user_ticket;
     let user = &ctx.accounts.user;
     let clock = Clock::get()?;
 
+    // Ticket sales stop while paused program wide or for this pool
+    ctx.accounts.global_state.require_not_paused(PAUSE_BUY)?;
+    require!(
+        lottery_pool.status != PoolStatus::Paused,
+        FortuneXError::PoolPaused
+    );
+
     // Validate given quantity
     require!(quantity > 0, FortuneXError::InvalidTicketQuantity);
 
@@ -22,66 +37,174 @@ pub fn buy_ticket(ctx: Context<BuyTicket>, pool_id: u64, quantity: u64) -> Resul
 
     // Check if pool has reached maximum tickets
     require!(
-        lottery_pool.tickets_sold.len() as u64 + quantity <= lottery_pool.max_tickets,
+        lottery_pool.tickets_sold + quantity <= lottery_pool.max_tickets,
         FortuneXError::PoolFull
     );
 
     let tickets_price = lottery_pool.ticket_price * quantity;
 
-    // Validate ticket price matches expected amount
-    require!(
-        ctx.accounts.user_token_account.amount >= tickets_price,
-        FortuneXError::InsufficientFunds
-    );
+    let vault = Vault::new(
+        &lottery_pool.mint,
+        ctx.accounts.vault_authority.to_account_info(),
+        optional_info(&ctx.accounts.pool_token_account),
+        ctx.accounts.mint.as_ref(),
+        optional_info(&ctx.accounts.token_program),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
 
-    // Transfer USDC from user to pool vault
-    let transfer_instruction = Transfer {
-        from: ctx.accounts.user_token_account.to_account_info(),
-        to: ctx.accounts.pool_token_account.to_account_info(),
-        authority: user.to_account_info(),
+    // Validate ticket price matches expected amount
+    let balance = if lottery_pool.is_native_sol() {
+        user.lamports()
+    } else {
+        ctx.accounts
+            .user_token_account
+            .as_ref()
+            .map_or(0, |a| a.amount)
+    };
+    require!(balance >= tickets_price, FortuneXError::InsufficientFunds);
+
+    // Transfer tickets price from user to pool vault
+    let vault_balance = ctx
+        .accounts
+        .pool_token_account
+        .as_ref()
+        .map_or(0, |a| a.amount);
+    vault.deposit(
+        user.to_account_info(),
+        optional_info(&ctx.accounts.user_token_account),
+        tickets_price,
+    )?;
+
+    // Mints with a transfer fee deliver less than was sent, so the pool is credited
+    // with what the vault actually received and tickets are refunded at that price
+    let received = match ctx.accounts.pool_token_account.as_mut() {
+        Some(pool_token_account) if !lottery_pool.is_native_sol() => {
+            pool_token_account.reload()?;
+            pool_token_account.amount - vault_balance
+        }
+        // Lamports arrive in full
+        _ => tickets_price,
     };
+    let amount_per_ticket = received / quantity;
+    // Only what the tickets are refunded at is credited, the rounding remainder
+    // stays in the vault as surplus for sweep_surplus
+    let credited = amount_per_ticket * quantity;
+
+    // Users without tickets in the round become participants
+    if user_ticket.tickets.is_empty() {
+        lottery_pool.participants += 1;
+    }
 
-    let cpi_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        transfer_instruction,
-    );
+    // Reissue cancelled ticket numbers first, as far as the user and the current ticket page
+    // have ranges free for them
+    let free_ranges = UserTicket::MAX_TICKET_RANGES
+        .saturating_sub(user_ticket.tickets.len())
+        .min(RANGES_PER_PAGE - lottery_pool.range_count as usize % RANGES_PER_PAGE);
+    let reissued = lottery_pool.reissue_tickets(quantity, free_ranges);
+
+    // Every run of reissued numbers is recorded as a range of its own in the ticket ledger,
+    // overriding the range the tickets were first bought in
+    let mut ticket_page = ctx.accounts.ticket_page.load_mut()?;
+    for run in reissued.chunk_by(|a, b| *b == a + 1) {
+        ticket_page.ranges[lottery_pool.range_count as usize % RANGES_PER_PAGE] = TicketRange {
+            start_ticket: run[0],
+            count: run.len() as u64,
+            owner: user.key(),
+        };
+        lottery_pool.range_count += 1;
+
+        user_ticket.add_tickets(
+            run[0],
+            run.len() as u64,
+            amount_per_ticket,
+            clock.unix_timestamp,
+        )?;
+        msg!(
+            "User {} was reissued tickets #{} to #{} for pool {} at price {}",
+            user.key(),
+            run[0],
+            run[run.len() - 1],
+            pool_id,
+            lottery_pool.ticket_price
+        );
+    }
 
-    token::transfer(cpi_ctx, tickets_price)?;
+    // Issue the remaining tickets as a single new range
+    let count = quantity - reissued.len() as u64;
+    let start_ticket = lottery_pool.next_ticket;
+    if count > 0 {
+        // Record the range in the ticket ledger
+        ticket_page.ranges[lottery_pool.range_count as usize % RANGES_PER_PAGE] = TicketRange {
+            start_ticket,
+            count,
+            owner: user.key(),
+        };
 
-    // update user ticket details
-    for _i in 0..quantity {
-        let popped_ticket = lottery_pool.cancelled_tickets.pop();
-        let next_ticket_number = popped_ticket.unwrap_or(lottery_pool.tickets_sold.len() as u64);
+        user_ticket.add_tickets(start_ticket, count, amount_per_ticket, clock.unix_timestamp)?;
 
-        let new_ticket = TicketDetails {
-            ticket_number: next_ticket_number,
-            amount_paid: lottery_pool.ticket_price,
-            timestamp: clock.unix_timestamp,
-        };
         msg!(
-            "User {} bought ticket #{} for pool {} at price {}",
+            "User {} bought tickets #{} to #{} for pool {} at price {}",
             user.key(),
-            new_ticket.ticket_number,
+            start_ticket,
+            start_ticket + count - 1,
             pool_id,
             lottery_pool.ticket_price
         );
 
-        user_ticket.tickets.push(new_ticket);
+        lottery_pool.next_ticket += count;
+        lottery_pool.range_count += 1;
+    }
+
+    // Update lottery pool state
+    lottery_pool.tickets_sold += quantity;
+    lottery_pool.prize_pool += credited;
+    ctx.accounts
+        .global_state
+        .add_escrow(&lottery_pool.mint, credited)?;
+
+    // Record the entropy commitment for this purchase, revealed later through reveal_entropy
+    if let Some(commitment) = commitment {
+        require!(
+            clock.unix_timestamp < lottery_pool.reveal_window_start(),
+            FortuneXError::CommitWindowClosed
+        );
+        require!(
+            lottery_pool.commitment_count < LotteryPool::MAX_COMMITMENTS
+                && user_ticket.commitments.len() < UserTicket::MAX_COMMITMENTS_PER_USER,
+            FortuneXError::TooManyCommitments
+        );
 
-        // Update lottery pool state
-        lottery_pool.tickets_sold.push(user.key());
-        lottery_pool.prize_pool += lottery_pool.ticket_price;
+        user_ticket.commitments.push(EntropyCommitment {
+            commitment,
+            revealed: false,
+        });
+        lottery_pool.commitment_count += 1;
     }
 
     user_ticket.user = user.key();
     user_ticket.pool = lottery_pool.key();
     user_ticket.pool_id = pool_id;
+    user_ticket.round = lottery_pool.round;
     user_ticket.bump = ctx.bumps.user_ticket;
 
     // Check if pool is now full and ready for draw
-    if lottery_pool.tickets_sold.len() as u64 == lottery_pool.max_tickets {
+    if lottery_pool.tickets_sold == lottery_pool.max_tickets {
         lottery_pool.status = PoolStatus::PoolFull;
     }
 
+    emit!(TicketsBought {
+        pool_id,
+        round: lottery_pool.round,
+        user: user.key(),
+        quantity,
+        reissued_tickets: reissued,
+        start_ticket,
+        count,
+        amount: received,
+        prize_pool: lottery_pool.prize_pool,
+        tickets_sold: lottery_pool.tickets_sold,
+        timestamp: clock.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,28 @@
+use crate::instructions::CancelAuthorityTransfer;
+use crate::{AuthorityTransferCancelled, FortuneXError};
+use anchor_lang::prelude::*;
+
+pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+
+    // Validate there is a transfer to cancel
+    require!(
+        global_state.pending_authority != Pubkey::default(),
+        FortuneXError::NoPendingAuthority
+    );
+
+    msg!(
+        "Authority transfer to {} cancelled",
+        global_state.pending_authority
+    );
+
+    emit!(AuthorityTransferCancelled {
+        authority: global_state.authority,
+        pending_authority: global_state.pending_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    global_state.pending_authority = Pubkey::default();
+
+    Ok(())
+}
@@ -1,47 +1,54 @@
 use crate::enums::PoolStatus;
+use crate::handlers::{optional_info, Vault};
 use crate::instructions::CancelTicket;
-use crate::FortuneXError;
+use crate::{FortuneXError, TicketCancelled, PAUSE_CANCEL};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer};
 
 pub fn cancel_ticket(ctx: Context<CancelTicket>, pool_id: u64, ticket_number: u64) -> Result<()> {
     let lottery_pool = &mut ctx.accounts.lottery_pool;
     let user_ticket = &mut ctx.accounts.user_ticket;
-    let global_state = &ctx.accounts.global_state;
     let user = &ctx.accounts.user;
 
+    // Cancellations stop while paused program wide or for this pool
+    ctx.accounts.global_state.require_not_paused(PAUSE_CANCEL)?;
+    require!(
+        lottery_pool.status != PoolStatus::Paused,
+        FortuneXError::PoolPaused
+    );
+
     // Validate pool is active
     require!(
         lottery_pool.status != PoolStatus::Completed,
         FortuneXError::PoolNotActive
     );
 
-    // check whether user owns given ticket
-    let maybe_ticket_details = {
-        let tickets = &user_ticket.tickets;
-        tickets
-            .iter()
-            .find(|t| t.ticket_number == ticket_number)
-            .cloned()
-    };
-
-    let cancelled_ticket = match maybe_ticket_details {
-        Some(t) => t,
-        None => {
-            msg!("User does not own ticket {}", ticket_number);
-            return err!(FortuneXError::TicketNotFound);
-        }
-    };
-
-    // cancel ticket
-    user_ticket
-        .tickets
-        .retain(|t| t.ticket_number != ticket_number);
-
-    let amount_paid = cancelled_ticket.amount_paid;
+    // Refunding pools return tickets in full through refund_ticket
+    require!(
+        lottery_pool.status != PoolStatus::Refunding,
+        FortuneXError::PoolRefunding
+    );
+
+    // Tickets are locked once randomness has been requested
+    require!(
+        lottery_pool.status != PoolStatus::Drawing
+            && lottery_pool.status != PoolStatus::Settling,
+        FortuneXError::PoolAlreadyDrawing
+    );
+
+    // Validate there is room to track the cancelled ticket until it is reissued
+    require!(
+        lottery_pool.can_cancel_ticket(),
+        FortuneXError::TooManyCancelledTickets
+    );
+
+    // cancel ticket, removing it from the user's range that holds it
+    let amount_paid = user_ticket.remove_ticket(ticket_number)?;
+    if user_ticket.tickets.is_empty() {
+        lottery_pool.participants -= 1;
+    }
 
     // Calculate cancellation fee using platform fee basis points (bps)
-    let cancellation_fee = (amount_paid * global_state.platform_fee_bps as u64) / 10000;
+    let cancellation_fee = (amount_paid * lottery_pool.platform_fee_bps as u64) / 10000;
     let refund_amount = amount_paid - cancellation_fee;
 
     // Create vault authority signer seeds
@@ -52,52 +59,36 @@ pub fn cancel_ticket(ctx: Context<CancelTicket>, pool_id: u64, ticket_number: u6
     ];
     let vault_signer = &[&vault_authority_seeds[..]];
 
-    // Refund amount from pool vault to user
-    let refund_instruction = Transfer {
-        from: ctx.accounts.pool_token_account.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
-        authority: ctx.accounts.vault_authority.to_account_info(),
-    };
-
-    let refund_cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        refund_instruction,
-        vault_signer,
-    );
-
-    token::transfer(refund_cpi_ctx, refund_amount)?;
-
-    // Transfer cancellation fee to platform token account
-    let transfer_to_platform = Transfer {
-        from: ctx.accounts.pool_token_account.to_account_info(),
-        to: ctx.accounts.platform_token_account.to_account_info(),
-        authority: ctx.accounts.vault_authority.to_account_info(),
-    };
-
-    let platform_cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        transfer_to_platform,
-        vault_signer,
-    );
-
-    token::transfer(platform_cpi_ctx, cancellation_fee)?;
-
-    // Update lottery pool state
-    let user_key = user.key();
+    let vault = Vault::new(
+        &lottery_pool.mint,
+        ctx.accounts.vault_authority.to_account_info(),
+        optional_info(&ctx.accounts.pool_token_account),
+        ctx.accounts.mint.as_ref(),
+        optional_info(&ctx.accounts.token_program),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
 
-    // find occurence of user key in tickets sold
-    let index_to_remove = {
-        let tickets = &lottery_pool.tickets_sold;
-        tickets.iter().position(|k| k == &user_key)
-    };
-
-    // remove user key from tickets_sold
-    if let Some(index) = index_to_remove {
-        lottery_pool.tickets_sold.remove(index);
-    }
-
-    lottery_pool.prize_pool -= cancelled_ticket.amount_paid;
-    lottery_pool.cancelled_tickets.push(ticket_number);
+    // Refund amount from pool vault to user
+    let user_recipient = vault.recipient(
+        Some(user.to_account_info()),
+        optional_info(&ctx.accounts.user_token_account),
+    )?;
+    vault.pay(user_recipient, refund_amount, vault_signer)?;
+
+    // Transfer cancellation fee to the platform
+    let platform_recipient = vault.recipient(
+        optional_info(&ctx.accounts.platform_wallet),
+        optional_info(&ctx.accounts.platform_token_account),
+    )?;
+    vault.pay(platform_recipient, cancellation_fee, vault_signer)?;
+
+    // Update lottery pool state, the vacated ticket is skipped by the draw until it is reissued
+    lottery_pool.tickets_sold -= 1;
+    lottery_pool.prize_pool -= amount_paid;
+    ctx.accounts
+        .global_state
+        .release_escrow(&lottery_pool.mint, amount_paid)?;
+    lottery_pool.cancel_ticket_number(ticket_number);
 
     // Check if pool status is full and update status
     if lottery_pool.status == PoolStatus::PoolFull {
@@ -111,13 +102,24 @@ pub fn cancel_ticket(ctx: Context<CancelTicket>, pool_id: u64, ticket_number: u6
         pool_id,
     );
 
-    // Optionally close the account if it's empty
+    emit!(TicketCancelled {
+        pool_id,
+        round: lottery_pool.round,
+        user: user.key(),
+        ticket_number,
+        amount_paid,
+        refund_amount,
+        cancellation_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    // Close the account once its last ticket is cancelled, returning the rent to the user
     if user_ticket.tickets.is_empty() {
         msg!(
             "No tickets left. Closing user_ticket account {}",
             user.key()
         );
-        return Ok(());
+        ctx.accounts.user_ticket.close(user.to_account_info())?;
     }
 
     Ok(())
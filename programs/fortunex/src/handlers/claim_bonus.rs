@@ -0,0 +1,71 @@
+use crate::handlers::{optional_info, Vault};
+use crate::instructions::ClaimBonus;
+use crate::{BonusClaimed, FortuneXError, PAUSE_CLAIM};
+use anchor_lang::prelude::*;
+
+pub fn claim_bonus(ctx: Context<ClaimBonus>, round_id: u64, place: u8) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let bonus_history = &mut ctx.accounts.bonus_history;
+
+    // Claims stop while paused program wide
+    global_state.require_not_paused(PAUSE_CLAIM)?;
+
+    // Validate the place belongs to the claiming winner and is unclaimed
+    let prize = bonus_history
+        .winners
+        .get(place as usize)
+        .ok_or(FortuneXError::InvalidPrizePlace)?;
+    require!(
+        prize.winner == ctx.accounts.winner.key(),
+        FortuneXError::InvalidWinner
+    );
+    require!(!prize.claimed, FortuneXError::PrizeAlreadyClaimed);
+    let (winner, amount) = (prize.winner, prize.amount);
+
+    // Create bonus authority signer seeds
+    let bonus_authority_seeds = &[
+        b"bonus_authority".as_ref(),
+        bonus_history.mint.as_ref(),
+        &[ctx.bumps.bonus_authority],
+    ];
+    let bonus_signer = &[&bonus_authority_seeds[..]];
+
+    // Transfer prize to winner, signed by the bonus authority PDA
+    let vault = Vault::new(
+        &bonus_history.mint,
+        ctx.accounts.bonus_authority.to_account_info(),
+        optional_info(&ctx.accounts.bonus_pool_token_account),
+        ctx.accounts.mint.as_ref(),
+        optional_info(&ctx.accounts.token_program),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+    let recipient = vault.recipient(
+        Some(ctx.accounts.winner.to_account_info()),
+        optional_info(&ctx.accounts.winner_token_account),
+    )?;
+    vault.pay(recipient, amount, bonus_signer)?;
+
+    bonus_history.winners[place as usize].claimed = true;
+    global_state
+        .accepted_mint_mut(&bonus_history.mint)?
+        .bonus_reserved -= amount;
+
+    msg!(
+        "Winner {} claimed {} for place {} in bonus round {}",
+        winner,
+        amount,
+        place + 1,
+        round_id
+    );
+
+    emit!(BonusClaimed {
+        round_id,
+        mint: bonus_history.mint,
+        place,
+        winner,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
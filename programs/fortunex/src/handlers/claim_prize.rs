@@ -0,0 +1,91 @@
+use crate::enums::PoolStatus;
+use crate::handlers::{optional_info, Vault};
+use crate::instructions::ClaimPrize;
+use crate::{FortuneXError, PrizeClaimed, PAUSE_CLAIM};
+use anchor_lang::prelude::*;
+
+pub fn claim_prize(
+    ctx: Context<ClaimPrize>,
+    pool_id: u64,
+    draw_index: u64,
+    place: u8,
+) -> Result<()> {
+    let lottery_pool = &mut ctx.accounts.lottery_pool;
+    let draw_history = &mut ctx.accounts.draw_history;
+
+    // Claims stop while paused program wide or for this pool
+    ctx.accounts.global_state.require_not_paused(PAUSE_CLAIM)?;
+    require!(
+        lottery_pool.status != PoolStatus::Paused,
+        FortuneXError::PoolPaused
+    );
+
+    // Validate the round has been drawn, recurring pools move on to the next round after a draw
+    require!(
+        draw_history.round < lottery_pool.round || lottery_pool.status == PoolStatus::Completed,
+        FortuneXError::PoolNotActive
+    );
+
+    // Validate the place belongs to the claiming winner and is unclaimed
+    let prize = draw_history
+        .winners
+        .get(place as usize)
+        .ok_or(FortuneXError::InvalidPrizePlace)?;
+    require!(
+        prize.winner == ctx.accounts.winner.key(),
+        FortuneXError::InvalidWinner
+    );
+    require!(!prize.claimed, FortuneXError::PrizeAlreadyClaimed);
+    let (winner, amount) = (prize.winner, prize.amount);
+
+    // Create vault authority signer seeds
+    let vault_authority_seeds = &[
+        b"vault_authority".as_ref(),
+        &pool_id.to_le_bytes(),
+        &[ctx.bumps.vault_authority],
+    ];
+    let vault_signer = &[&vault_authority_seeds[..]];
+
+    // Transfer prize to winner
+    let vault = Vault::new(
+        &lottery_pool.mint,
+        ctx.accounts.vault_authority.to_account_info(),
+        optional_info(&ctx.accounts.pool_token_account),
+        ctx.accounts.mint.as_ref(),
+        optional_info(&ctx.accounts.token_program),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+    let recipient = vault.recipient(
+        Some(ctx.accounts.winner.to_account_info()),
+        optional_info(&ctx.accounts.winner_token_account),
+    )?;
+    vault.pay(recipient, amount, vault_signer)?;
+
+    draw_history.winners[place as usize].claimed = true;
+    lottery_pool.unclaimed_prizes -= amount;
+    ctx.accounts
+        .global_state
+        .release_escrow(&lottery_pool.mint, amount)?;
+
+    msg!(
+        "Winner {} claimed {} for place {} in pool {} round {} (draw {})",
+        winner,
+        amount,
+        place + 1,
+        pool_id,
+        draw_history.round,
+        draw_index
+    );
+
+    emit!(PrizeClaimed {
+        pool_id,
+        round: draw_history.round,
+        draw_index,
+        place,
+        winner,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
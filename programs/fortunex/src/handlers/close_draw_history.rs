@@ -0,0 +1,44 @@
+use crate::enums::PoolStatus;
+use crate::instructions::CloseDrawHistory;
+use crate::{DrawHistoryClosed, FortuneXError, LotteryPool};
+use anchor_lang::prelude::*;
+
+pub fn close_draw_history(
+    ctx: Context<CloseDrawHistory>,
+    pool_id: u64,
+    draw_index: u64,
+) -> Result<()> {
+    let draw_history = &ctx.accounts.draw_history;
+    let pool_info = ctx.accounts.lottery_pool.to_account_info();
+
+    // Pools closed through close_pool are settled, otherwise the pool must have moved past the round
+    if !pool_info.data_is_empty() {
+        let lottery_pool = LotteryPool::try_deserialize(&mut &pool_info.try_borrow_data()?[..])?;
+        let settled =
+            draw_history.round < lottery_pool.round || lottery_pool.status == PoolStatus::Completed;
+        require!(settled, FortuneXError::PoolNotSettled);
+    }
+
+    // Validate every prize of the draw was claimed, the record is what winners claim against
+    require!(
+        draw_history.winners.iter().all(|w| w.claimed),
+        FortuneXError::PrizesNotClaimed
+    );
+
+    msg!(
+        "Closed draw history {} of pool {} round {}",
+        draw_index,
+        pool_id,
+        draw_history.round
+    );
+
+    emit!(DrawHistoryClosed {
+        pool_id,
+        round: draw_history.round,
+        draw_index,
+        payer: draw_history.payer,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use crate::handlers::vault_balance;
+use crate::instructions::ClosePool;
+use crate::{FortuneXError, PoolClosed, TicketPage};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{self, CloseAccount};
+
+pub fn close_pool<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClosePool<'info>>,
+    pool_id: u64,
+) -> Result<()> {
+    let lottery_pool = &ctx.accounts.lottery_pool;
+
+    // Validate the pool is done taking and refunding tickets
+    require!(lottery_pool.is_finished(), FortuneXError::PoolNotSettled);
+
+    // Validate every prize was claimed and nothing is left in the vault, surplus is swept first
+    let balance = vault_balance(
+        &lottery_pool.mint,
+        &ctx.accounts.vault_authority,
+        ctx.accounts.pool_token_account.as_ref(),
+    )?;
+    require!(
+        lottery_pool.unclaimed_prizes == 0 && balance == 0,
+        FortuneXError::PoolNotEmpty
+    );
+
+    // Close the ticket pages with the pool, passed as (ticket page, payer) pairs with every
+    // page of the ledger once. Each page's rent goes back to whoever paid for it.
+    require!(
+        ctx.remaining_accounts.len() == 2 * lottery_pool.page_count as usize,
+        FortuneXError::TicketPagesNotClosed
+    );
+    let mut closed = vec![false; lottery_pool.page_count as usize];
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let ticket_page = AccountLoader::<TicketPage>::try_from(&pair[0])?;
+        let page_index = {
+            let page = ticket_page.load()?;
+            require!(
+                page.pool == lottery_pool.key() && page.payer == pair[1].key(),
+                FortuneXError::InvalidTicketPage
+            );
+            page.page_index as usize
+        };
+        require!(
+            page_index < closed.len() && !closed[page_index],
+            FortuneXError::InvalidTicketPage
+        );
+        closed[page_index] = true;
+        ticket_page.close(pair[1].clone())?;
+    }
+
+    // Create vault authority signer seeds
+    let vault_authority_seeds = &[
+        b"vault_authority".as_ref(),
+        &pool_id.to_le_bytes(),
+        &[ctx.bumps.vault_authority],
+    ];
+    let vault_signer = &[&vault_authority_seeds[..]];
+
+    // Close the vault, returning its rent to the creator
+    if lottery_pool.is_native_sol() {
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_authority.to_account_info(),
+                to: ctx.accounts.creator.to_account_info(),
+            },
+            vault_signer,
+        );
+        system_program::transfer(cpi_ctx, ctx.accounts.vault_authority.lamports())?;
+    } else {
+        let (Some(pool_token_account), Some(token_program)) = (
+            ctx.accounts.pool_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+        ) else {
+            return err!(FortuneXError::MissingTokenAccounts);
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            CloseAccount {
+                account: pool_token_account.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            vault_signer,
+        );
+        token_interface::close_account(cpi_ctx)?;
+    }
+
+    msg!(
+        "Pool {} closed, rent returned to {}",
+        pool_id,
+        ctx.accounts.creator.key()
+    );
+
+    emit!(PoolClosed {
+        pool_id,
+        creator: ctx.accounts.creator.key(),
+        ticket_pages: lottery_pool.page_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::instructions::CloseRecentDraws;
+use crate::{FortuneXError, RecentDrawsClosed};
+use anchor_lang::prelude::*;
+
+pub fn close_recent_draws(ctx: Context<CloseRecentDraws>) -> Result<()> {
+    // Validate every pool is settled, so no draw is left to record
+    require!(
+        ctx.accounts.global_state.active_pools == 0,
+        FortuneXError::PoolsStillActive
+    );
+
+    msg!(
+        "Closed recent draws, rent returned to {}",
+        ctx.accounts.payer.key()
+    );
+
+    emit!(RecentDrawsClosed {
+        payer: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
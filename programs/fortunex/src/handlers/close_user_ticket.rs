@@ -0,0 +1,35 @@
+use crate::enums::PoolStatus;
+use crate::instructions::CloseUserTicket;
+use crate::{FortuneXError, LotteryPool, UserTicketClosed};
+use anchor_lang::prelude::*;
+
+pub fn close_user_ticket(ctx: Context<CloseUserTicket>, pool_id: u64, round: u64) -> Result<()> {
+    let user_ticket = &ctx.accounts.user_ticket;
+    let pool_info = ctx.accounts.lottery_pool.to_account_info();
+
+    // Pools closed through close_pool are settled, otherwise the round must be drawn
+    // or refunded before its tickets can go
+    if !pool_info.data_is_empty() {
+        let lottery_pool = LotteryPool::try_deserialize(&mut &pool_info.try_borrow_data()?[..])?;
+        let settled = round < lottery_pool.round
+            || lottery_pool.is_finished()
+            || (lottery_pool.status == PoolStatus::Refunding && user_ticket.tickets.is_empty());
+        require!(settled, FortuneXError::PoolNotSettled);
+    }
+
+    msg!(
+        "Closed user_ticket account of {} for pool {} round {}",
+        ctx.accounts.user.key(),
+        pool_id,
+        round
+    );
+
+    emit!(UserTicketClosed {
+        pool_id,
+        round,
+        user: ctx.accounts.user.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
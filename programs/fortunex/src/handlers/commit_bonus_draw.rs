@@ -0,0 +1,60 @@
+use crate::handlers::{committed_slot_hash, CommittedSlotHash};
+use crate::instructions::CommitBonusDraw;
+use crate::{
+    BonusDrawCommitted, FortuneXError, DRAW_COMMIT_SLOT_DELAY, DRAW_RECOMMIT_PENALTY_SLOTS,
+    PAUSE_DRAW,
+};
+use anchor_lang::prelude::*;
+
+pub fn commit_bonus_draw(ctx: Context<CommitBonusDraw>, round_id: u64) -> Result<()> {
+    let bonus_round = &mut ctx.accounts.bonus_round;
+    let clock = Clock::get()?;
+
+    // Draws stop while paused program wide
+    ctx.accounts.global_state.require_not_paused(PAUSE_DRAW)?;
+
+    // Validate the round is over and not drawn yet
+    require!(!bonus_round.drawn, FortuneXError::BonusAlreadyDrawn);
+    require!(
+        clock.unix_timestamp >= bonus_round.end_time,
+        FortuneXError::BonusRoundNotEnded
+    );
+
+    // Re-commit is only allowed once the committed slot can no longer be drawn
+    if bonus_round.draw_commit_slot != 0 {
+        require!(
+            matches!(
+                committed_slot_hash(&ctx.accounts.slot_hashes, bonus_round.draw_commit_slot)?,
+                CommittedSlotHash::Expired
+            ),
+            FortuneXError::DrawAlreadyCommitted
+        );
+
+        // and the penalty window passed, the same as for pool draws
+        let recommit_slot = bonus_round
+            .draw_commit_slot
+            .checked_add(DRAW_RECOMMIT_PENALTY_SLOTS)
+            .ok_or(FortuneXError::Overflow)?;
+        require!(
+            clock.slot >= recommit_slot,
+            FortuneXError::DrawRecommitTooEarly
+        );
+    }
+
+    // Entries are closed, commit to a slot whose hash nobody knows yet
+    bonus_round.draw_commit_slot = clock.slot + DRAW_COMMIT_SLOT_DELAY;
+
+    msg!(
+        "Bonus draw for round {} committed to slot {}",
+        round_id,
+        bonus_round.draw_commit_slot
+    );
+
+    emit!(BonusDrawCommitted {
+        round_id,
+        commit_slot: bonus_round.draw_commit_slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::enums::PoolStatus;
+use crate::handlers::{committed_slot_hash, CommittedSlotHash};
+use crate::instructions::CommitDraw;
+use crate::{
+    DrawCommitted, DrawRolledOver, FortuneXError, PoolRefunding, DRAW_COMMIT_SLOT_DELAY,
+    DRAW_RECOMMIT_PENALTY_SLOTS, PAUSE_DRAW,
+};
+use anchor_lang::prelude::*;
+
+pub fn commit_draw(ctx: Context<CommitDraw>, pool_id: u64) -> Result<()> {
+    let lottery_pool = &mut ctx.accounts.lottery_pool;
+    let clock = Clock::get()?;
+
+    // Draws stop while paused program wide or for this pool
+    ctx.accounts.global_state.require_not_paused(PAUSE_DRAW)?;
+    require!(
+        lottery_pool.status != PoolStatus::Paused,
+        FortuneXError::PoolPaused
+    );
+
+    // Validate pool is ready for draw
+    require!(
+        lottery_pool.status != PoolStatus::Completed,
+        FortuneXError::PoolDrawCompleted
+    );
+    require!(
+        lottery_pool.status != PoolStatus::Refunding,
+        FortuneXError::PoolRefunding
+    );
+    require!(
+        lottery_pool.status != PoolStatus::Settling,
+        FortuneXError::PoolAlreadyDrawing
+    );
+
+    if lottery_pool.status == PoolStatus::Drawing && lottery_pool.vrf_account != Pubkey::default() {
+        // Pools waiting on a VRF result can only be settled through settle_draw,
+        // unless the oracle never answered and the request timed out
+        require!(
+            lottery_pool.is_vrf_request_expired(clock.unix_timestamp)?,
+            FortuneXError::PoolAlreadyDrawing
+        );
+
+        msg!("Cancelling the unanswered VRF request of pool {}", pool_id);
+        lottery_pool.vrf_account = Pubkey::default();
+        lottery_pool.vrf_request_round = 0;
+        lottery_pool.vrf_requested_at = 0;
+    } else if lottery_pool.status == PoolStatus::Drawing {
+        // Re-commit is only allowed once the committed slot can no longer be drawn
+        require!(
+            matches!(
+                committed_slot_hash(&ctx.accounts.slot_hashes, lottery_pool.draw_commit_slot)?,
+                CommittedSlotHash::Expired
+            ),
+            FortuneXError::DrawAlreadyCommitted
+        );
+
+        // and the penalty window passed, so letting an unfavourable slot expire
+        // buys a fresh slot only once in a long while
+        let recommit_slot = lottery_pool
+            .draw_commit_slot
+            .checked_add(DRAW_RECOMMIT_PENALTY_SLOTS)
+            .ok_or(FortuneXError::Overflow)?;
+        require!(
+            clock.slot >= recommit_slot,
+            FortuneXError::DrawRecommitTooEarly
+        );
+    } else {
+        // Check if draw time has arrived
+        require!(
+            clock.unix_timestamp >= lottery_pool.draw_time,
+            FortuneXError::DrawTimeNotReached
+        );
+
+        // Check if pool has minimum number of tickets sold
+        // if not, roll the draw over or refund the tickets depending on the pool policy
+        if lottery_pool.tickets_sold < lottery_pool.min_tickets {
+            let round = lottery_pool.round;
+            if lottery_pool.roll_over_or_refund(clock.unix_timestamp)? {
+                msg!("Updated draw time of pool {}", pool_id);
+
+                emit!(DrawRolledOver {
+                    pool_id,
+                    round,
+                    rollovers: lottery_pool.rollovers,
+                    draw_time: lottery_pool.draw_time,
+                    timestamp: clock.unix_timestamp,
+                });
+            } else {
+                msg!("Pool {} did not sell enough tickets, refunding", pool_id);
+
+                // A one-off pool without tickets has nothing left to refund
+                if lottery_pool.is_finished() {
+                    ctx.accounts.global_state.release_pool()?;
+                }
+
+                emit!(PoolRefunding {
+                    pool_id,
+                    round,
+                    tickets_sold: lottery_pool.tickets_sold,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+
+            return Ok(()); // return early
+        }
+    }
+
+    // Close ticket sales and commit to a slot whose hash nobody knows yet
+    lottery_pool.status = PoolStatus::Drawing;
+    lottery_pool.draw_commit_slot = clock.slot + DRAW_COMMIT_SLOT_DELAY;
+
+    msg!(
+        "Draw for pool {} committed to slot {}",
+        pool_id,
+        lottery_pool.draw_commit_slot
+    );
+
+    emit!(DrawCommitted {
+        pool_id,
+        round: lottery_pool.round,
+        commit_slot: lottery_pool.draw_commit_slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
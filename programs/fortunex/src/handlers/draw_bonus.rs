@@ -0,0 +1,109 @@
+use crate::handlers::{committed_slot_hash, pick_winning_tickets, CommittedSlotHash};
+use crate::instructions::DrawBonus;
+use crate::{BonusDrawCompleted, BonusWinner, FortuneXError, PAUSE_DRAW};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+pub fn draw_bonus(ctx: Context<DrawBonus>, round_id: u64) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let bonus_round = &mut ctx.accounts.bonus_round;
+    let bonus_history = &mut ctx.accounts.bonus_history;
+    let clock = Clock::get()?;
+
+    // Draws stop while paused program wide
+    global_state.require_not_paused(PAUSE_DRAW)?;
+
+    // The draw must have been committed to a future slot through commit_bonus_draw
+    require!(!bonus_round.drawn, FortuneXError::BonusAlreadyDrawn);
+    require!(
+        bonus_round.draw_commit_slot != 0,
+        FortuneXError::DrawNotCommitted
+    );
+
+    let (slot_hash_slot, slot_hash) =
+        match committed_slot_hash(&ctx.accounts.slot_hashes, bonus_round.draw_commit_slot)? {
+            CommittedSlotHash::Available(slot, hash) => (slot, hash),
+            CommittedSlotHash::Pending => return err!(FortuneXError::DrawCommitSlotNotReached),
+            CommittedSlotHash::Expired => return err!(FortuneXError::DrawCommitSlotExpired),
+        };
+    let random_seed = hashv(&[b"bonus", &slot_hash, &round_id.to_le_bytes()]).to_bytes();
+
+    // Distribute what the bonus pool holds beyond the prizes of earlier rounds not claimed yet
+    // The native SOL bonus pool keeps its rent exempt minimum
+    let balance = if bonus_round.mint == Pubkey::default() {
+        ctx.accounts
+            .bonus_authority
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0))
+    } else {
+        ctx.accounts
+            .bonus_pool_token_account
+            .as_ref()
+            .ok_or(FortuneXError::MissingTokenAccounts)?
+            .amount
+    };
+    let accepted_mint = global_state.accepted_mint_mut(&bonus_round.mint)?;
+    let available = balance.saturating_sub(accepted_mint.bonus_reserved);
+
+    // Places without a participant are not paid, their share stays in the bonus pool
+    let total_participants = bonus_round.participants.len() as u64;
+    let places = bonus_round
+        .prize_shares_bps
+        .len()
+        .min(bonus_round.participants.len());
+
+    let mut winners = Vec::with_capacity(places);
+    let mut prize_amount = 0;
+    for (place, position) in pick_winning_tickets(&random_seed, total_participants, places)
+        .into_iter()
+        .enumerate()
+    {
+        let amount = (available * bonus_round.prize_shares_bps[place] as u64) / 10000;
+        prize_amount += amount;
+
+        winners.push(BonusWinner {
+            place: place as u8,
+            winner: bonus_round.participants[position as usize],
+            amount,
+            claimed: false,
+        });
+    }
+
+    // Prizes stay in the bonus pool until claimed
+    accepted_mint.bonus_reserved += prize_amount;
+    bonus_round.drawn = true;
+
+    // Record bonus draw history
+    bonus_history.round_id = round_id;
+    bonus_history.prize_amount = prize_amount;
+    bonus_history.total_participants = total_participants;
+    bonus_history.draw_timestamp = clock.unix_timestamp;
+    bonus_history.random_seed = random_seed;
+    bonus_history.slot_hash_slot = slot_hash_slot;
+    bonus_history.slot_hash = slot_hash;
+    bonus_history.mint = bonus_round.mint;
+    bonus_history.bump = ctx.bumps.bonus_history;
+
+    for w in winners.iter() {
+        msg!(
+            "Bonus round {}: place {} won by {}, prize {}",
+            round_id,
+            w.place + 1,
+            w.winner,
+            w.amount
+        );
+    }
+
+    emit!(BonusDrawCompleted {
+        round_id,
+        mint: bonus_round.mint,
+        total_participants,
+        prize_amount,
+        random_seed,
+        winners: winners.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+    bonus_history.winners = winners;
+
+    Ok(())
+}
@@ -1,103 +1,120 @@
-use crate::enums::PoolStatus;
-use crate::instructions::DrawWinner;
-use crate::FortuneXError;
+use crate::enums::{PoolStatus, RandomnessSource};
+use crate::handlers::{optional_info, Vault};
+use crate::instructions::{DrawWinner, DrawWinnerBumps};
+use crate::{DrawCompleted, DrawHistory, DrawSummary, FortuneXError, PrizeWinner, PAUSE_DRAW};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer};
+use anchor_lang::solana_program::hash::hashv;
 
-pub fn draw_winner<'info>(
-    ctx: Context<'_, '_, '_, 'info, DrawWinner<'info>>,
-    pool_id: u64,
-) -> Result<()> {
+pub fn draw_winner(ctx: Context<DrawWinner>, pool_id: u64) -> Result<()> {
     let lottery_pool = &mut ctx.accounts.lottery_pool;
-    let draw_history = &mut ctx.accounts.draw_history;
-    let global_state = &ctx.accounts.global_state;
-    let clock = Clock::get()?;
-
-    // Check if crank account is whitelisted (only whitelisted creators can draw pools)
-    require!(
-        global_state.is_creator_whitelisted(&ctx.accounts.crank.key()),
-        crate::FortuneXError::Unauthorized
-    );
 
-    // Validate pool is ready for draw
+    // Draws stop while paused program wide or for this pool
+    ctx.accounts.global_state.require_not_paused(PAUSE_DRAW)?;
     require!(
-        lottery_pool.status != PoolStatus::Completed,
-        FortuneXError::PoolDrawCompleted
+        lottery_pool.status != PoolStatus::Paused,
+        FortuneXError::PoolPaused
     );
 
-    // Check if draw time has arrived
+    // The draw must have been committed to a future slot through commit_draw
     require!(
-        clock.unix_timestamp >= lottery_pool.draw_time,
-        FortuneXError::DrawTimeNotReached
+        lottery_pool.status == PoolStatus::Drawing && lottery_pool.draw_commit_slot != 0,
+        FortuneXError::DrawNotCommitted
     );
 
-    // Check if pool has minimum number of tickets sold
-    // if not, increase draw time
-    if (lottery_pool.tickets_sold.len() as u64) < lottery_pool.min_tickets {
-        lottery_pool.draw_time = clock.unix_timestamp + lottery_pool.draw_interval;
-
-        msg!("Updated draw time of pool {}", pool_id);
+    // Generate random seed from the revealed participant entropy and the committed slot hash
+    let (slot_hash_slot, slot_hash) =
+        match committed_slot_hash(&ctx.accounts.slot_hashes, lottery_pool.draw_commit_slot)? {
+            CommittedSlotHash::Available(slot, hash) => (slot, hash),
+            CommittedSlotHash::Pending => return err!(FortuneXError::DrawCommitSlotNotReached),
+            CommittedSlotHash::Expired => return err!(FortuneXError::DrawCommitSlotExpired),
+        };
+    let random_seed =
+        hashv(&[&lottery_pool.entropy, &slot_hash, &pool_id.to_le_bytes()]).to_bytes();
+
+    // Store the transcript so anyone can recompute the seed, before the round is reset
+    let draw_history = &mut ctx.accounts.draw_history;
+    draw_history.slot_hash_slot = slot_hash_slot;
+    draw_history.slot_hash = slot_hash;
+    draw_history.reveals = lottery_pool.reveals.clone();
 
-        return Ok(()); // return early
-    }
+    complete_draw(
+        ctx.accounts,
+        &ctx.bumps,
+        pool_id,
+        random_seed,
+        RandomnessSource::SlotHashes {
+            slot: slot_hash_slot,
+        },
+    )
+}
 
-    // Validate that remaining accounts match participants
-    require!(
-        ctx.remaining_accounts.len() == lottery_pool.tickets_sold.len(),
-        FortuneXError::InvalidRemainingAccountsCount
-    );
+pub(crate) enum CommittedSlotHash {
+    Pending,                  // Target slot has not been produced yet
+    Available(u64, [u8; 32]), // First slot at or after the target and its hash
+    Expired,                  // Target slot fell out of the sysvar
+}
 
-    // Generate random seed using clock and pool data
-    let random_seed = {
-        let mut seed = [0u8; 32];
-        let clock_bytes = clock.unix_timestamp.to_le_bytes();
-        let pool_bytes = pool_id.to_le_bytes();
-        let slot_bytes = clock.slot.to_le_bytes();
+// Look up the hash of the first slot at or after target_slot in the SlotHashes
+// sysvar. The account data is a u64 entry count followed by
+// (slot: u64, hash: [u8; 32]) pairs, newest first.
+pub(crate) fn committed_slot_hash(
+    slot_hashes: &AccountInfo,
+    target_slot: u64,
+) -> Result<CommittedSlotHash> {
+    let data = slot_hashes.try_borrow_data()?;
+    require!(data.len() >= 8, FortuneXError::InvalidSlotHashes);
+
+    let len = u64::from_le_bytes(data[0..8].try_into().unwrap()) as usize;
+    require!(data.len() >= 8 + len * 40, FortuneXError::InvalidSlotHashes);
+
+    let entry = |i: usize| {
+        let offset = 8 + i * 40;
+        let slot = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&data[offset + 8..offset + 40]);
+        (slot, hash)
+    };
 
-        // Combine different entropy sources
-        seed[0..8].copy_from_slice(&clock_bytes);
-        seed[8..16].copy_from_slice(&pool_bytes);
-        seed[16..24].copy_from_slice(&slot_bytes);
-        seed[24..32].copy_from_slice(&lottery_pool.created_at.to_le_bytes());
+    let mut found = None;
+    for i in 0..len {
+        let (slot, hash) = entry(i);
+        if slot < target_slot {
+            break;
+        }
+        found = Some((i, slot, hash));
+    }
 
-        seed
-    };
+    Ok(match found {
+        None => CommittedSlotHash::Pending,
+        // Every entry is newer than the target: only usable if the oldest one is the target itself
+        Some((i, slot, _)) if i + 1 == len && slot != target_slot => CommittedSlotHash::Expired,
+        Some((_, slot, hash)) => CommittedSlotHash::Available(slot, hash),
+    })
+}
 
-    // Select random winner (ticket number 1-10)
-    let random_value = u64::from_le_bytes([
-        random_seed[0],
-        random_seed[1],
-        random_seed[2],
-        random_seed[3],
-        random_seed[4],
-        random_seed[5],
-        random_seed[6],
-        random_seed[7],
-    ]);
-    let winning_ticket = random_value % lottery_pool.tickets_sold.len() as u64;
-    let winner_index = winning_ticket as usize;
-    let winner = lottery_pool.tickets_sold[winner_index];
-
-    let accounts: Vec<_> = ctx.remaining_accounts.iter().collect();
-
-    // Get the winner's token account from remaining accounts
-    let winner_token_account = accounts[winner_index];
-
-    // Verify this is the correct ATA for the winner
-    let expected_ata = anchor_spl::associated_token::get_associated_token_address(
-        &winner,
-        &&ctx.accounts.global_state.usdc_mint.key(),
-    );
+// Pick the winning tickets from the given seed, pay out the fees and record the prizes.
+// Owners of the winning tickets are looked up in the ticket pages by resolve_winners.
+// Shared by draw_winner and settle_draw.
+pub(crate) fn complete_draw(
+    accounts: &mut DrawWinner,
+    bumps: &DrawWinnerBumps,
+    pool_id: u64,
+    random_seed: [u8; 32],
+    randomness: RandomnessSource,
+) -> Result<()> {
+    let lottery_pool = &mut accounts.lottery_pool;
+    let draw_history = &mut accounts.draw_history;
+    let clock = Clock::get()?;
 
-    require!(
-        winner_token_account.key() == expected_ata,
-        FortuneXError::InvalidWinnerTokenAccount
-    );
+    // Validate there is at least one ticket to draw from
+    require!(lottery_pool.tickets_sold > 0, FortuneXError::NoParticipants);
 
-    // Verify the account is owned by the token program
+    // Every place needs its own ticket
+    let total_tickets = lottery_pool.tickets_sold;
+    let places = lottery_pool.prize_shares_bps.len();
     require!(
-        winner_token_account.owner == &anchor_spl::token::ID,
-        FortuneXError::InvalidWinnerTokenAccount
+        total_tickets >= places as u64,
+        FortuneXError::NotEnoughTickets
     );
 
     // Calculate prize distribution
@@ -107,106 +124,194 @@ pub fn draw_winner<'info>(
     // bps = 100 bps means 1% platform fee, 1000 bps means 10%, 10,000 bps means 100%
     // Example: if platform_fee_bps = 100 (1%), and total_prize = 100_000_000 (100 USDC),
     // platform_fee = (100_000_000 * 100) / 10000 = 1_000_000 (1 USDC)
-    let platform_fee = (total_prize * global_state.platform_fee_bps as u64) / 10000;
-    let mut winner_prize = total_prize - platform_fee;
+    let platform_fee = (total_prize * lottery_pool.platform_fee_bps as u64) / 10000;
     // deduct bonus pool fee
-    let bonus_pool_fee = (total_prize * global_state.bonus_pool_fee_bps as u64) / 10000;
-    winner_prize -= bonus_pool_fee;
+    let bonus_pool_fee = (total_prize * lottery_pool.bonus_pool_fee_bps as u64) / 10000;
 
     // deduct pool creator fee
     let commission = (total_prize * lottery_pool.commission_bps as u64) / 10000;
-    winner_prize -= commission;
+
+    // deduct the bounty of whoever completes the draw
+    let crank_bounty = lottery_pool.crank_bounty_amount(total_prize);
+
+    let winner_prize = total_prize
+        .checked_sub(platform_fee + bonus_pool_fee + commission + crank_bounty)
+        .ok_or(FortuneXError::InvalidFeeTotal)?;
+
+    // Split what is left between the places of the prize schedule
+    let winning_tickets = pick_winning_tickets(&random_seed, total_tickets, places);
+    let mut winners = Vec::with_capacity(places);
+    let mut distributed = 0;
+    for (place, position) in winning_tickets.into_iter().enumerate() {
+        let amount = (winner_prize * lottery_pool.prize_shares_bps[place] as u64) / 10000;
+        distributed += amount;
+
+        winners.push(PrizeWinner {
+            place: place as u8,
+            ticket: lottery_pool.nth_live_ticket(position),
+            winner: Pubkey::default(), // Set by resolve_winners
+            amount,
+            claimed: false,
+        });
+    }
+    // Rounding dust goes to first place
+    winners[0].amount += winner_prize - distributed;
 
     // Create vault authority signer seeds
     let vault_authority_seeds = &[
         b"vault_authority".as_ref(),
         &pool_id.to_le_bytes(),
-        &[ctx.bumps.vault_authority],
+        &[bumps.vault_authority],
     ];
     let vault_signer = &[&vault_authority_seeds[..]];
 
-    // Transfer prize to winner
-    let transfer_to_winner = Transfer {
-        from: ctx.accounts.pool_token_account.to_account_info(),
-        to: winner_token_account.to_account_info(),
-        authority: ctx.accounts.vault_authority.to_account_info(),
-    };
-
-    let winner_cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        transfer_to_winner,
-        vault_signer,
-    );
-
-    token::transfer(winner_cpi_ctx, winner_prize)?;
+    let vault = Vault::new(
+        &lottery_pool.mint,
+        accounts.vault_authority.to_account_info(),
+        optional_info(&accounts.pool_token_account),
+        accounts.mint.as_ref(),
+        optional_info(&accounts.token_program),
+        accounts.system_program.to_account_info(),
+    )?;
 
     // Transfer platform fee
-    let transfer_to_platform = Transfer {
-        from: ctx.accounts.pool_token_account.to_account_info(),
-        to: ctx.accounts.platform_token_account.to_account_info(),
-        authority: ctx.accounts.vault_authority.to_account_info(),
-    };
-
-    let platform_cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        transfer_to_platform,
-        vault_signer,
-    );
-
-    token::transfer(platform_cpi_ctx, platform_fee)?;
+    let platform_recipient = vault.recipient(
+        optional_info(&accounts.platform_wallet),
+        optional_info(&accounts.platform_token_account),
+    )?;
+    vault.pay(platform_recipient, platform_fee, vault_signer)?;
 
     // Transfer bonus pool fee
-    let transfer_to_bonus_pool = Transfer {
-        from: ctx.accounts.pool_token_account.to_account_info(),
-        to: ctx.accounts.bonus_pool_token_account.to_account_info(),
-        authority: ctx.accounts.vault_authority.to_account_info(),
-    };
-
-    let bonus_cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        transfer_to_bonus_pool,
-        vault_signer,
-    );
-
-    token::transfer(bonus_cpi_ctx, bonus_pool_fee)?;
+    let bonus_recipient = vault.recipient(
+        Some(accounts.bonus_authority.to_account_info()),
+        optional_info(&accounts.bonus_pool_token_account),
+    )?;
+    vault.pay(bonus_recipient, bonus_pool_fee, vault_signer)?;
 
     // Transfer commission to pool creator
-    let transfer_to_creator = Transfer {
-        from: ctx.accounts.pool_token_account.to_account_info(),
-        to: ctx.accounts.creator_token_account.to_account_info(),
-        authority: ctx.accounts.vault_authority.to_account_info(),
-    };
+    let creator_recipient = vault.recipient(
+        optional_info(&accounts.creator),
+        optional_info(&accounts.creator_token_account),
+    )?;
+    vault.pay(creator_recipient, commission, vault_signer)?;
+
+    // Transfer bounty to the crank
+    let crank_recipient = vault.recipient(
+        Some(accounts.crank.to_account_info()),
+        optional_info(&accounts.crank_token_account),
+    )?;
+    vault.pay(crank_recipient, crank_bounty, vault_signer)?;
+
+    // The prizes stay in the vault until they are claimed
+    lottery_pool.unclaimed_prizes = lottery_pool
+        .unclaimed_prizes
+        .checked_add(winner_prize)
+        .ok_or(FortuneXError::Overflow)?;
+
+    // Record draw history under the next global draw index
+    let global_state = &mut accounts.global_state;
+    global_state.release_escrow(&lottery_pool.mint, total_prize - winner_prize)?;
+    let draw_index = global_state.draws_count;
+    global_state.draws_count = draw_index.checked_add(1).ok_or(FortuneXError::Overflow)?;
+    lottery_pool.draw_index = draw_index;
 
-    let commission_cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        transfer_to_creator,
-        vault_signer,
-    );
-
-    token::transfer(commission_cpi_ctx, commission)?;
-
-    // Update lottery pool status and winner details
-    lottery_pool.status = PoolStatus::Completed;
-    lottery_pool.winner = winner;
-
-    // Record draw history
     draw_history.pool = lottery_pool.key();
     draw_history.pool_id = pool_id;
-    draw_history.winner = winner;
+    draw_history.round = lottery_pool.round;
+    draw_history.draw_index = draw_index;
     draw_history.prize_amount = winner_prize;
-    draw_history.total_tickets = lottery_pool.tickets_sold.len() as u64;
+    draw_history.total_tickets = total_tickets;
     draw_history.draw_timestamp = clock.unix_timestamp;
-    draw_history.winning_ticket = winning_ticket;
     draw_history.random_seed = random_seed;
-    draw_history.bump = ctx.bumps.draw_history;
-
-    msg!(
-        "Draw completed for pool {}: Winner {} (ticket #{}) won {} USDC",
+    draw_history.bump = bumps.draw_history;
+
+    // Record the full breakdown so vault flows can be reconciled from the history
+    draw_history.version = DrawHistory::VERSION;
+    draw_history.total_prize = total_prize;
+    draw_history.platform_fee = platform_fee;
+    draw_history.bonus_pool_fee = bonus_pool_fee;
+    draw_history.commission = commission;
+    draw_history.crank_bounty = crank_bounty;
+    draw_history.platform_fee_bps = lottery_pool.platform_fee_bps;
+    draw_history.bonus_pool_fee_bps = lottery_pool.bonus_pool_fee_bps;
+    draw_history.commission_bps = lottery_pool.commission_bps;
+    draw_history.ticket_price = lottery_pool.ticket_price;
+    draw_history.participants = lottery_pool.participants;
+    draw_history.randomness = randomness;
+    draw_history.payer = accounts.crank.key();
+
+    // Keep the draw in the recent draws list
+    let recent_draws = &mut accounts.recent_draws;
+    recent_draws.bump = bumps.recent_draws;
+    if recent_draws.payer == Pubkey::default() {
+        recent_draws.payer = accounts.crank.key();
+    }
+    recent_draws.record(DrawSummary {
+        draw_index,
         pool_id,
-        winner,
-        winning_ticket,
-        winner_prize
-    );
+        round: lottery_pool.round,
+        mint: lottery_pool.mint,
+        prize_amount: winner_prize,
+        total_tickets,
+        draw_timestamp: clock.unix_timestamp,
+    });
+
+    for w in winners.iter() {
+        msg!(
+            "Draw completed for pool {}: place {} won by ticket #{}, prize {}",
+            pool_id,
+            w.place + 1,
+            w.ticket,
+            w.amount
+        );
+    }
+    emit!(DrawCompleted {
+        pool_id,
+        round: lottery_pool.round,
+        draw_index,
+        total_tickets,
+        prize_pool: total_prize,
+        platform_fee,
+        bonus_pool_fee,
+        commission,
+        crank: accounts.crank.key(),
+        crank_bounty,
+        winner_prize,
+        random_seed,
+        winners: winners.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+    draw_history.winners = winners;
+
+    // Wait for the ticket owners to be resolved from the ticket pages
+    lottery_pool.status = PoolStatus::Settling;
 
     Ok(())
 }
+
+// Draw `count` distinct positions among `total` live tickets without replacement,
+// deriving one random value per place from the seed
+pub(crate) fn pick_winning_tickets(random_seed: &[u8; 32], total: u64, count: usize) -> Vec<u64> {
+    let mut picked: Vec<u64> = Vec::with_capacity(count);
+
+    for place in 0..count {
+        let hash = hashv(&[random_seed, &(place as u64).to_le_bytes()]).to_bytes();
+        let random_value = u64::from_le_bytes(hash[0..8].try_into().unwrap());
+
+        // Position among the tickets that have not been picked yet
+        let mut ticket = random_value % (total - place as u64);
+        let mut taken = picked.clone();
+        taken.sort_unstable();
+        for t in taken {
+            if t <= ticket {
+                ticket += 1;
+            } else {
+                break;
+            }
+        }
+
+        picked.push(ticket);
+    }
+
+    picked
+}
@@ -0,0 +1,64 @@
+use crate::enums::PoolStatus;
+use crate::instructions::EnterBonusRound;
+use crate::{BonusRound, BonusRoundEntered, FortuneXError};
+use anchor_lang::prelude::*;
+
+pub fn enter_bonus_round(ctx: Context<EnterBonusRound>, round_id: u64) -> Result<()> {
+    let bonus_round = &mut ctx.accounts.bonus_round;
+    let user_ticket = &ctx.accounts.user_ticket;
+    let lottery_pool = &ctx.accounts.lottery_pool;
+    let clock = Clock::get()?;
+
+    // Validate the round still takes entries
+    require!(
+        clock.unix_timestamp < bonus_round.end_time,
+        FortuneXError::BonusRoundClosed
+    );
+
+    // Validate the tickets were bought in the mint the bonus pool pays out
+    require!(
+        lottery_pool.mint == bonus_round.mint,
+        FortuneXError::NotEligibleForBonus
+    );
+
+    // Validate the tickets can no longer be cancelled, their round is drawing or drawn
+    require!(
+        user_ticket.round < lottery_pool.round
+            || matches!(
+                lottery_pool.status,
+                PoolStatus::Drawing | PoolStatus::Settling | PoolStatus::Completed
+            ),
+        FortuneXError::NotEligibleForBonus
+    );
+
+    // Validate the user bought a ticket while the round was open
+    require!(
+        user_ticket.tickets.iter().any(|t| {
+            t.timestamp >= bonus_round.start_time && t.timestamp < bonus_round.end_time
+        }),
+        FortuneXError::NotEligibleForBonus
+    );
+
+    // Every participant is entered once, whatever the number of tickets bought
+    require!(
+        !bonus_round.participants.contains(&user_ticket.user),
+        FortuneXError::AlreadyEnteredBonus
+    );
+    require!(
+        bonus_round.participants.len() < BonusRound::MAX_PARTICIPANTS,
+        FortuneXError::BonusRoundFull
+    );
+
+    bonus_round.participants.push(user_ticket.user);
+
+    msg!("User {} entered bonus round {}", user_ticket.user, round_id);
+
+    emit!(BonusRoundEntered {
+        round_id,
+        user: user_ticket.user,
+        participants: bonus_round.participants.len() as u64,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
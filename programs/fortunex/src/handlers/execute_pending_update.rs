@@ -0,0 +1,44 @@
+use crate::instructions::ExecutePendingUpdate;
+use crate::{FortuneXError, PendingUpdateExecuted};
+use anchor_lang::prelude::*;
+
+pub fn execute_pending_update(ctx: Context<ExecutePendingUpdate>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    // Validate an update is queued and its delay has passed
+    let update = global_state
+        .pending_update
+        .clone()
+        .ok_or(FortuneXError::NoPendingUpdate)?;
+    require!(
+        clock.unix_timestamp >= update.effective_at,
+        FortuneXError::UpdateNotReady
+    );
+
+    global_state.apply_update(&update)?;
+    global_state.pending_update = None;
+
+    msg!("--- Global State Updated ---");
+    msg!("Platform wallet: {}", global_state.platform_wallet);
+    msg!("Platform fee: {} bps", global_state.platform_fee_bps);
+    msg!("Bonus Pool fee: {} bps", global_state.bonus_pool_fee_bps);
+    msg!("Crank bounty: {:?}", global_state.crank_bounty);
+    msg!(
+        "Max creator commission: {} bps",
+        global_state.max_creator_commission_bps
+    );
+    msg!("Update delay: {} seconds", global_state.update_delay);
+
+    emit!(PendingUpdateExecuted {
+        platform_wallet: global_state.platform_wallet,
+        platform_fee_bps: global_state.platform_fee_bps,
+        bonus_pool_fee_bps: global_state.bonus_pool_fee_bps,
+        crank_bounty: global_state.crank_bounty,
+        max_creator_commission_bps: global_state.max_creator_commission_bps,
+        update_delay: global_state.update_delay,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use crate::{instructions::Initialize, GlobalState};
+use crate::enums::CrankBounty;
+use crate::{
+    instructions::Initialize, AcceptedMint, GlobalState, DEFAULT_MAX_CREATOR_COMMISSION_BPS,
+};
 use anchor_lang::prelude::*;
 
 pub fn initialize(
@@ -7,6 +10,7 @@ pub fn initialize(
     usdc_mint: Pubkey,
     platform_fee_bps: u16,
     bonus_pool_fee_bps: u16,
+    update_delay: i64,
 ) -> Result<()> {
     let global_state = &mut ctx.accounts.global_state;
 
@@ -16,22 +20,43 @@ pub fn initialize(
     // Validate bonus pool fee is reasonable
     GlobalState::validate_bonus_pool_fee_bps(bonus_pool_fee_bps)?;
 
+    // Validate update delay is reasonable
+    GlobalState::validate_update_delay(update_delay)?;
+
     // Initialize global state
     global_state.authority = ctx.accounts.authority.key();
+    global_state.pending_authority = Pubkey::default();
     global_state.platform_wallet = platform_wallet;
     global_state.usdc_mint = usdc_mint;
     global_state.platform_fee_bps = platform_fee_bps;
     global_state.bonus_pool_fee_bps = bonus_pool_fee_bps;
+    global_state.crank_bounty = CrankBounty::Fixed { amount: 0 }; // set later through update_global_state
+    global_state.max_creator_commission_bps = DEFAULT_MAX_CREATOR_COMMISSION_BPS;
     global_state.pools_count = 0;
+    global_state.vrf_program = Pubkey::default(); // set later through update_global_state
+    global_state.vrf_account = Pubkey::default(); // set later through update_global_state
+    global_state.active_pools = 0;
+    global_state.update_delay = update_delay;
+    global_state.pending_update = None;
+    global_state.guardian = Pubkey::default(); // set later through update_global_state
+    global_state.paused = 0;
+    global_state.draws_count = 0;
     global_state.creators_whitelist = vec![ctx.accounts.authority.key()];
+    global_state.accepted_mints = vec![AcceptedMint {
+        mint: ctx.accounts.usdc_mint.key(),
+        enabled: true,
+        bonus_reserved: 0,
+        escrowed: 0,
+    }];
     global_state.bump = ctx.bumps.global_state;
 
     msg!("FortuneX lottery program initialized successfully!");
     msg!("Authority: {}", global_state.authority);
     msg!("Platform wallet: {}", global_state.platform_wallet);
-    msg!("USDC mint: {}", global_state.usdc_mint);
+    msg!("Default mint: {}", global_state.usdc_mint);
     msg!("Platform fee: {} bps", global_state.platform_fee_bps);
     msg!("Bonus Pool fee: {} bps", global_state.platform_fee_bps);
+    msg!("Update delay: {} seconds", global_state.update_delay);
 
     Ok(())
 }
@@ -1,14 +1,20 @@
-use crate::enums::PoolStatus;
-use crate::instructions::InitializePool;
-use crate::LotteryPool;
+use crate::enums::{MinTicketsPolicy, PoolStatus, PrizeSchedule};
+use crate::instructions::{InitializePool, MintKey};
+use crate::{LotteryPool, PoolCreated, MAX_DRAW_INTERVAL, MIN_DRAW_INTERVAL};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_pool(
     ctx: Context<InitializePool>,
     ticket_price: u64,
     min_tickets: u64,
     max_tickets: u64,
     draw_interval: i64,
+    prize_schedule: PrizeSchedule,
+    recurring: bool,
+    min_tickets_policy: MinTicketsPolicy,
+    commission_bps: u16,
 ) -> Result<()> {
     let global_state = &mut ctx.accounts.global_state;
     let lottery_pool = &mut ctx.accounts.lottery_pool;
@@ -23,11 +29,25 @@ pub fn initialize_pool(
     // Validate min_tickets cannot be greater than max_tickets
     require!(max_tickets >= min_tickets, crate::FortuneXError::InvalidMinMaxTickets);
 
-    // // Validate draw interval (minimum 1 hour, maximum 7 days)
-    // require!(
-    //     draw_interval >= 3600 && draw_interval <= 604800,
-    //     crate::FortuneXError::InvalidDrawInterval
-    // );
+    // Validate prize schedule, every place needs a ticket before the pool can be drawn
+    let prize_shares_bps = LotteryPool::prize_shares(&prize_schedule)?;
+    require!(
+        min_tickets >= prize_shares_bps.len() as u64,
+        crate::FortuneXError::InvalidPrizeSchedule
+    );
+
+    // Validate creator commission is within the global cap and leaves a prize
+    require!(
+        commission_bps <= global_state.max_creator_commission_bps,
+        crate::FortuneXError::InvalidCommission
+    );
+    global_state.validate_fee_total(commission_bps)?;
+
+    // Validate draw interval (minimum 1 hour, maximum 7 days)
+    require!(
+        (MIN_DRAW_INTERVAL..=MAX_DRAW_INTERVAL).contains(&draw_interval),
+        crate::FortuneXError::InvalidDrawInterval
+    );
 
     // Check if creator is whitelisted (only whitelisted creators can create pools)
     require!(
@@ -35,6 +55,38 @@ pub fn initialize_pool(
         crate::FortuneXError::CreatorNotWhitelisted
     );
 
+    // Validate the mint is accepted, pools without a mint account are paid in native SOL
+    let mint = ctx.accounts.mint.mint_key();
+    require!(
+        global_state.is_mint_accepted(&mint),
+        crate::FortuneXError::MintNotAccepted
+    );
+
+    if mint == Pubkey::default() {
+        // Fund the vault up to its rent exempt minimum so paying out a share of the
+        // lamports it holds never leaves it below rent
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        let top_up = rent_exempt.saturating_sub(ctx.accounts.vault_authority.lamports());
+        if top_up > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.vault_authority.to_account_info(),
+                },
+            );
+            system_program::transfer(cpi_ctx, top_up)?;
+        }
+    } else {
+        // Token pools need their vault and the creator's token account
+        require!(
+            ctx.accounts.pool_token_account.is_some()
+                && ctx.accounts.creator_token_account.is_some()
+                && ctx.accounts.token_program.is_some(),
+            crate::FortuneXError::MissingTokenAccounts
+        );
+    }
+
     // Initialize lottery pool
     lottery_pool.pool_id = global_state.pools_count;
     lottery_pool.status = PoolStatus::Active;
@@ -42,31 +94,75 @@ pub fn initialize_pool(
     lottery_pool.ticket_price = ticket_price;
     lottery_pool.min_tickets = min_tickets;
     lottery_pool.max_tickets = max_tickets;
-    lottery_pool.tickets_sold = Vec::new();
+    lottery_pool.tickets_sold = 0;
     lottery_pool.draw_interval = draw_interval;
     lottery_pool.draw_time = clock.unix_timestamp + draw_interval;
     lottery_pool.created_at = clock.unix_timestamp;
     lottery_pool.creator = ctx.accounts.authority.key();
-    lottery_pool.commission_bps = LotteryPool::DEFAULT_COMMISSION_BPS;
+    lottery_pool.commission_bps = commission_bps;
     lottery_pool.bump = ctx.bumps.lottery_pool;
     lottery_pool.cancelled_tickets = Vec::new();
+    lottery_pool.vrf_account = Pubkey::default();
+    lottery_pool.vrf_request_round = 0;
+    lottery_pool.vrf_requested_at = 0;
+    lottery_pool.draw_commit_slot = 0;
+    lottery_pool.commitment_count = 0;
+    lottery_pool.entropy = [0u8; 32];
+    lottery_pool.reveals = Vec::new();
+    lottery_pool.winners = Vec::new();
+    lottery_pool.prize_shares_bps = prize_shares_bps;
+    lottery_pool.round = 0;
+    lottery_pool.recurring = recurring;
+    lottery_pool.min_tickets_policy = min_tickets_policy;
+    lottery_pool.rollovers = 0;
+    lottery_pool.next_ticket = 0;
+    lottery_pool.page_count = 0;
+    lottery_pool.platform_fee_bps = global_state.platform_fee_bps;
+    lottery_pool.bonus_pool_fee_bps = global_state.bonus_pool_fee_bps;
+    lottery_pool.crank_bounty = global_state.crank_bounty;
+    lottery_pool.mint = mint;
+    lottery_pool.paused_status = PoolStatus::Active;
+    lottery_pool.draw_index = 0;
+    lottery_pool.participants = 0;
+    lottery_pool.unclaimed_prizes = 0;
 
     // Increment global pools count
     global_state.pools_count = global_state
         .pools_count
         .checked_add(1)
         .ok_or(crate::FortuneXError::Overflow)?;
+    global_state.active_pools = global_state
+        .active_pools
+        .checked_add(1)
+        .ok_or(crate::FortuneXError::Overflow)?;
 
     msg!("New lottery pool created successfully!");
     msg!("Pool ID: {}", lottery_pool.pool_id);
     msg!("Creator: {}", ctx.accounts.authority.key());
     msg!("Draw interval: {} seconds", draw_interval);
+    msg!("Recurring: {}", recurring);
+    msg!("Creator commission: {} bps", commission_bps);
+    msg!("Mint: {}", lottery_pool.mint);
     msg!("Next draw time: {}", lottery_pool.draw_time);
-    msg!(
-        "Pool token account: {}",
-        ctx.accounts.pool_token_account.key()
-    );
+    if let Some(pool_token_account) = &ctx.accounts.pool_token_account {
+        msg!("Pool token account: {}", pool_token_account.key());
+    }
     msg!("Vault authority: {}", ctx.accounts.vault_authority.key());
 
+    emit!(PoolCreated {
+        pool_id: lottery_pool.pool_id,
+        creator: lottery_pool.creator,
+        mint: lottery_pool.mint,
+        ticket_price,
+        min_tickets,
+        max_tickets,
+        draw_time: lottery_pool.draw_time,
+        recurring,
+        commission_bps,
+        platform_fee_bps: lottery_pool.platform_fee_bps,
+        bonus_pool_fee_bps: lottery_pool.bonus_pool_fee_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
     Ok(())
 }
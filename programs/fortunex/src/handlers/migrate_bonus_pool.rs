@@ -0,0 +1,59 @@
+use crate::instructions::MigrateBonusPool;
+use crate::{BonusPoolMigrated, BONUS_AUTHORITY_SEED};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, TransferChecked};
+
+pub fn migrate_bonus_pool(ctx: Context<MigrateBonusPool>) -> Result<()> {
+    let amount = ctx.accounts.legacy_bonus_pool_token_account.amount;
+
+    // Create legacy bonus authority signer seeds
+    let legacy_authority_seeds = &[BONUS_AUTHORITY_SEED, &[ctx.bumps.legacy_bonus_authority]];
+    let legacy_signer = &[&legacy_authority_seeds[..]];
+
+    // Move the whole balance into the bonus pool of the default mint
+    if amount > 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx
+                    .accounts
+                    .legacy_bonus_pool_token_account
+                    .to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.bonus_pool_token_account.to_account_info(),
+                authority: ctx.accounts.legacy_bonus_authority.to_account_info(),
+            },
+            legacy_signer,
+        );
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+    }
+
+    // Close the empty legacy account, its rent goes back to the authority
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx
+                .accounts
+                .legacy_bonus_pool_token_account
+                .to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.legacy_bonus_authority.to_account_info(),
+        },
+        legacy_signer,
+    );
+    token_interface::close_account(cpi_ctx)?;
+
+    msg!(
+        "Moved {} from the legacy bonus pool to the bonus pool of mint {}",
+        amount,
+        ctx.accounts.mint.key()
+    );
+
+    emit!(BonusPoolMigrated {
+        mint: ctx.accounts.mint.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
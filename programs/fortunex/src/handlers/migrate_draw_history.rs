@@ -0,0 +1,73 @@
+use crate::enums::RandomnessSource;
+use crate::instructions::MigrateDrawHistory;
+use crate::{DrawHistory, DrawHistoryMigrated, FortuneXError, LegacyDrawHistory, PrizeWinner};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+pub fn migrate_draw_history(ctx: Context<MigrateDrawHistory>, pool_id: u64) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let draw_history = &mut ctx.accounts.draw_history;
+    let legacy_info = ctx.accounts.legacy_draw_history.to_account_info();
+
+    // Read the record in the layout of the first release, it kept the account name
+    // so it starts with the same discriminator
+    let legacy = {
+        let data = legacy_info.try_borrow_data()?;
+        require!(
+            data.starts_with(DrawHistory::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyDrawHistory::deserialize(&mut &data[DrawHistory::DISCRIMINATOR.len()..])
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize)?
+    };
+    require!(legacy.pool_id == pool_id, FortuneXError::InvalidDrawHistory);
+
+    let draw_index = global_state.draws_count;
+    global_state.draws_count = global_state
+        .draws_count
+        .checked_add(1)
+        .ok_or(FortuneXError::Overflow)?;
+
+    // The first release paid its single winner during the draw and recorded neither
+    // the fee breakdown nor where the randomness came from, those stay zero
+    draw_history.pool = legacy.pool;
+    draw_history.pool_id = legacy.pool_id;
+    draw_history.round = 0;
+    draw_history.draw_index = draw_index;
+    draw_history.prize_amount = legacy.prize_amount;
+    draw_history.total_tickets = legacy.total_tickets;
+    draw_history.draw_timestamp = legacy.draw_timestamp;
+    draw_history.random_seed = legacy.random_seed;
+    draw_history.bump = ctx.bumps.draw_history;
+    draw_history.winners = vec![PrizeWinner {
+        place: 0,
+        ticket: legacy.winning_ticket,
+        winner: legacy.winner,
+        amount: legacy.prize_amount,
+        claimed: true,
+    }];
+    draw_history.version = DrawHistory::VERSION;
+    draw_history.randomness = RandomnessSource::Unknown;
+    draw_history.payer = ctx.accounts.payer.key();
+
+    // Close the old record, its rent goes to the payer of the new one
+    let rent = legacy_info.lamports();
+    ctx.accounts.payer.add_lamports(rent)?;
+    legacy_info.sub_lamports(rent)?;
+    legacy_info.assign(&system_program::ID);
+    legacy_info.resize(0)?;
+
+    msg!(
+        "Draw history of pool {} migrated to draw index {}",
+        pool_id,
+        draw_index
+    );
+
+    emit!(DrawHistoryMigrated {
+        pool_id,
+        draw_index,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,15 +1,79 @@
+pub mod accept_authority;
+pub mod add_ticket_page;
 pub mod buy_ticket;
+pub mod cancel_authority_transfer;
 pub mod cancel_ticket;
+pub mod claim_bonus;
+pub mod claim_prize;
+pub mod close_draw_history;
+pub mod close_pool;
+pub mod close_recent_draws;
+pub mod close_user_ticket;
+pub mod commit_bonus_draw;
+pub mod commit_draw;
+pub mod draw_bonus;
 pub mod draw_winner;
+pub mod enter_bonus_round;
+pub mod execute_pending_update;
 pub mod initialize;
 pub mod initialize_pool;
+pub mod migrate_bonus_pool;
+pub mod migrate_draw_history;
+pub mod propose_authority;
+pub mod refund_all;
+pub mod refund_ticket;
+pub mod request_draw;
+pub mod resolve_winners;
+pub mod reveal_entropy;
+pub mod set_pause;
+pub mod set_pool_pause;
+pub mod settle_draw;
+pub mod start_bonus_round;
+pub mod stop_pool;
+pub mod sweep_surplus;
+pub mod update_mint_allowlist;
+pub mod update_pool_commission;
 pub mod update_whitelist;
 pub mod update_global_state;
+pub mod vault;
+pub mod verify_pool;
 
+pub use accept_authority::*;
+pub use add_ticket_page::*;
 pub use buy_ticket::*;
+pub use cancel_authority_transfer::*;
 pub use cancel_ticket::*;
+pub use claim_bonus::*;
+pub use claim_prize::*;
+pub use close_draw_history::*;
+pub use close_pool::*;
+pub use close_recent_draws::*;
+pub use close_user_ticket::*;
+pub use commit_bonus_draw::*;
+pub use commit_draw::*;
+pub use draw_bonus::*;
 pub use draw_winner::*;
+pub use enter_bonus_round::*;
+pub use execute_pending_update::*;
 pub use initialize::*;
 pub use initialize_pool::*;
+pub use migrate_bonus_pool::*;
+pub use migrate_draw_history::*;
+pub use propose_authority::*;
+pub use refund_all::*;
+pub use refund_ticket::*;
+pub use request_draw::*;
+pub use resolve_winners::*;
+pub use reveal_entropy::*;
+pub use set_pause::*;
+pub use set_pool_pause::*;
+pub use settle_draw::*;
+pub use start_bonus_round::*;
+pub use stop_pool::*;
+pub use sweep_surplus::*;
+pub use update_mint_allowlist::*;
+pub use update_pool_commission::*;
 pub use update_whitelist::*;
 pub use update_global_state::*;
+pub use vault::*;
+pub use verify_pool::*;
@@ -0,0 +1,30 @@
+use crate::instructions::ProposeAuthority;
+use crate::{AuthorityProposed, FortuneXError};
+use anchor_lang::prelude::*;
+
+pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+
+    // Validate the proposed authority
+    require!(
+        new_authority != Pubkey::default() && new_authority != global_state.authority,
+        FortuneXError::InvalidPendingAuthority
+    );
+
+    // Replaces any earlier proposal
+    global_state.pending_authority = new_authority;
+
+    msg!(
+        "Authority transfer from {} to {} proposed",
+        global_state.authority,
+        new_authority
+    );
+
+    emit!(AuthorityProposed {
+        authority: global_state.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use crate::enums::PoolStatus;
+use crate::handlers::{optional_info, Vault};
+use crate::instructions::RefundAll;
+use crate::{FortuneXError, TicketsRefunded, UserTicket};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+// usize::is_multiple_of needs Rust 1.87, newer than the rustc of older Solana platform tools
+#[allow(unknown_lints, clippy::manual_is_multiple_of)]
+pub fn refund_all<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefundAll<'info>>,
+    pool_id: u64,
+) -> Result<()> {
+    let lottery_pool = &mut ctx.accounts.lottery_pool;
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    // Validate pool was declared failed
+    require!(
+        lottery_pool.status == PoolStatus::Refunding,
+        FortuneXError::PoolNotRefunding
+    );
+
+    // Validate remaining accounts come in (user_ticket, user_token_account or wallet) pairs
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() % 2 == 0,
+        FortuneXError::InvalidRefundAccounts
+    );
+
+    // Create vault authority signer seeds
+    let vault_authority_seeds = &[
+        b"vault_authority".as_ref(),
+        &pool_id.to_le_bytes(),
+        &[ctx.bumps.vault_authority],
+    ];
+    let vault_signer = &[&vault_authority_seeds[..]];
+
+    let vault = Vault::new(
+        &lottery_pool.mint,
+        ctx.accounts.vault_authority.to_account_info(),
+        optional_info(&ctx.accounts.pool_token_account),
+        ctx.accounts.mint.as_ref(),
+        optional_info(&ctx.accounts.token_program),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let mut user_ticket = Account::<UserTicket>::try_from(&pair[0])?;
+
+        // Validate the tickets belong to the round being refunded and the refund goes to their owner
+        require!(
+            user_ticket.pool == lottery_pool.key() && user_ticket.round == lottery_pool.round,
+            FortuneXError::InvalidRefundAccounts
+        );
+        if lottery_pool.is_native_sol() {
+            require!(
+                pair[1].key() == user_ticket.user && pair[1].is_writable,
+                FortuneXError::InvalidRefundAccounts
+            );
+        } else {
+            let user_token_account = InterfaceAccount::<TokenAccount>::try_from(&pair[1])?;
+            require!(
+                user_token_account.mint == lottery_pool.mint
+                    && user_token_account.owner == user_ticket.user,
+                FortuneXError::InvalidRefundAccounts
+            );
+        }
+
+        let tickets = user_ticket.ticket_count();
+        let amount = user_ticket.amount_paid();
+        if tickets == 0 {
+            continue;
+        }
+
+        // Refund the full amount paid, no platform fee is taken from failed pools
+        vault.pay(pair[1].clone(), amount, vault_signer)?;
+
+        user_ticket.tickets.clear();
+        user_ticket.exit(&crate::ID)?;
+
+        let round = lottery_pool.round;
+        lottery_pool.record_refund(tickets, amount, clock.unix_timestamp)?;
+        global_state.release_escrow(&lottery_pool.mint, amount)?;
+
+        // The pool no longer holds ticket money once its last ticket is refunded
+        if lottery_pool.is_finished() {
+            global_state.release_pool()?;
+        }
+
+        msg!(
+            "Refunded {} tickets of user {} in pool {}: amount {}",
+            tickets,
+            user_ticket.user,
+            pool_id,
+            amount
+        );
+
+        emit!(TicketsRefunded {
+            pool_id,
+            round,
+            user: user_ticket.user,
+            tickets,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::enums::PoolStatus;
+use crate::handlers::{optional_info, Vault};
+use crate::instructions::RefundTicket;
+use crate::{FortuneXError, TicketsRefunded};
+use anchor_lang::prelude::*;
+
+pub fn refund_ticket(ctx: Context<RefundTicket>, pool_id: u64, ticket_number: u64) -> Result<()> {
+    let lottery_pool = &mut ctx.accounts.lottery_pool;
+    let user_ticket = &mut ctx.accounts.user_ticket;
+    let user = &ctx.accounts.user;
+    let clock = Clock::get()?;
+
+    // Validate pool was declared failed
+    require!(
+        lottery_pool.status == PoolStatus::Refunding,
+        FortuneXError::PoolNotRefunding
+    );
+
+    // Remove the ticket from the user's range that holds it
+    let amount_paid = user_ticket.remove_ticket(ticket_number)?;
+
+    // Create vault authority signer seeds
+    let vault_authority_seeds = &[
+        b"vault_authority".as_ref(),
+        &pool_id.to_le_bytes(),
+        &[ctx.bumps.vault_authority],
+    ];
+    let vault_signer = &[&vault_authority_seeds[..]];
+
+    // Refund the full amount paid, no platform fee is taken from failed pools
+    let vault = Vault::new(
+        &lottery_pool.mint,
+        ctx.accounts.vault_authority.to_account_info(),
+        optional_info(&ctx.accounts.pool_token_account),
+        ctx.accounts.mint.as_ref(),
+        optional_info(&ctx.accounts.token_program),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+    let recipient = vault.recipient(
+        Some(user.to_account_info()),
+        optional_info(&ctx.accounts.user_token_account),
+    )?;
+    vault.pay(recipient, amount_paid, vault_signer)?;
+
+    // Recurring pools move on to the next round once their last ticket is refunded
+    let round = lottery_pool.round;
+    lottery_pool.record_refund(1, amount_paid, clock.unix_timestamp)?;
+    ctx.accounts
+        .global_state
+        .release_escrow(&lottery_pool.mint, amount_paid)?;
+
+    // The pool no longer holds ticket money once its last ticket is refunded
+    if lottery_pool.is_finished() {
+        ctx.accounts.global_state.release_pool()?;
+    }
+
+    msg!(
+        "Refunded ticket #{} of user {} in pool {}: amount {}",
+        ticket_number,
+        user.key(),
+        pool_id,
+        amount_paid
+    );
+
+    emit!(TicketsRefunded {
+        pool_id,
+        round,
+        user: user.key(),
+        tickets: 1,
+        amount: amount_paid,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
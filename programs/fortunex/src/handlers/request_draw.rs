@@ -0,0 +1,110 @@
+use crate::enums::PoolStatus;
+use crate::instructions::RequestDraw;
+use crate::{DrawRequested, DrawRolledOver, FortuneXError, PoolRefunding, VrfResult, PAUSE_DRAW};
+use anchor_lang::prelude::*;
+
+pub fn request_draw(ctx: Context<RequestDraw>, pool_id: u64) -> Result<()> {
+    let lottery_pool = &mut ctx.accounts.lottery_pool;
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    // Draws stop while paused program wide or for this pool
+    global_state.require_not_paused(PAUSE_DRAW)?;
+    require!(
+        lottery_pool.status != PoolStatus::Paused,
+        FortuneXError::PoolPaused
+    );
+
+    // Validate pool is ready for draw
+    require!(
+        lottery_pool.status != PoolStatus::Completed,
+        FortuneXError::PoolDrawCompleted
+    );
+    require!(
+        lottery_pool.status != PoolStatus::Refunding,
+        FortuneXError::PoolRefunding
+    );
+    require!(
+        lottery_pool.status != PoolStatus::Settling,
+        FortuneXError::PoolAlreadyDrawing
+    );
+
+    if lottery_pool.status == PoolStatus::Drawing {
+        // A pending request is only replaced once it timed out, or once the oracle moved
+        // past the round it was waiting for, which settle_draw can no longer use
+        let vrf = VrfResult::load(&ctx.accounts.vrf, global_state)?;
+        let round_missed = lottery_pool.vrf_account == ctx.accounts.vrf.key()
+            && vrf.round > lottery_pool.vrf_settle_round()?;
+        require!(
+            round_missed || lottery_pool.is_vrf_request_expired(clock.unix_timestamp)?,
+            FortuneXError::PoolAlreadyDrawing
+        );
+
+        msg!("Replacing the unanswered VRF request of pool {}", pool_id);
+    } else {
+        // Check if draw time has arrived
+        require!(
+            clock.unix_timestamp >= lottery_pool.draw_time,
+            FortuneXError::DrawTimeNotReached
+        );
+
+        // Check if pool has minimum number of tickets sold
+        // if not, roll the draw over or refund the tickets depending on the pool policy
+        if lottery_pool.tickets_sold < lottery_pool.min_tickets {
+            let round = lottery_pool.round;
+            if lottery_pool.roll_over_or_refund(clock.unix_timestamp)? {
+                msg!("Updated draw time of pool {}", pool_id);
+
+                emit!(DrawRolledOver {
+                    pool_id,
+                    round,
+                    rollovers: lottery_pool.rollovers,
+                    draw_time: lottery_pool.draw_time,
+                    timestamp: clock.unix_timestamp,
+                });
+            } else {
+                msg!("Pool {} did not sell enough tickets, refunding", pool_id);
+
+                // A one-off pool without tickets has nothing left to refund
+                if lottery_pool.is_finished() {
+                    global_state.release_pool()?;
+                }
+
+                emit!(PoolRefunding {
+                    pool_id,
+                    round,
+                    tickets_sold: lottery_pool.tickets_sold,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+
+            return Ok(()); // return early
+        }
+    }
+
+    let vrf = VrfResult::load(&ctx.accounts.vrf, global_state)?;
+
+    // Lock the pool until the oracle publishes the next round
+    lottery_pool.status = PoolStatus::Drawing;
+    lottery_pool.vrf_account = ctx.accounts.vrf.key();
+    lottery_pool.vrf_request_round = vrf.round;
+    lottery_pool.vrf_requested_at = clock.unix_timestamp;
+
+    msg!(
+        "Draw requested for pool {} on VRF account {} (round {})",
+        pool_id,
+        lottery_pool.vrf_account,
+        vrf.round
+    );
+
+    emit!(DrawRequested {
+        pool_id,
+        round: lottery_pool.round,
+        requester: ctx.accounts.crank.key(),
+        vrf_account: lottery_pool.vrf_account,
+        vrf_round: vrf.round,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use crate::enums::PoolStatus;
+use crate::instructions::ResolveWinners;
+use crate::{FortuneXError, TicketLedger, WinnersResolved, PAUSE_DRAW};
+use anchor_lang::prelude::*;
+
+pub fn resolve_winners<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveWinners<'info>>,
+    pool_id: u64,
+) -> Result<()> {
+    let lottery_pool = &mut ctx.accounts.lottery_pool;
+    let draw_history = &mut ctx.accounts.draw_history;
+    let clock = Clock::get()?;
+
+    // Draws stop while paused program wide or for this pool
+    ctx.accounts.global_state.require_not_paused(PAUSE_DRAW)?;
+    require!(
+        lottery_pool.status != PoolStatus::Paused,
+        FortuneXError::PoolPaused
+    );
+
+    // Validate the winning tickets have been drawn
+    require!(
+        lottery_pool.status == PoolStatus::Settling,
+        FortuneXError::WinnersNotDrawn
+    );
+
+    // Look up the owner of every winning ticket, reissued tickets belong to the owner of
+    // their latest range
+    let ledger = TicketLedger::load(
+        ctx.remaining_accounts,
+        &lottery_pool.key(),
+        lottery_pool.range_count,
+    )?;
+    let round = draw_history.round;
+    for w in draw_history.winners.iter_mut() {
+        w.winner = ledger.owner(w.ticket)?;
+
+        msg!(
+            "Pool {} round {}: place {} won by {} (ticket #{}), can claim {}",
+            pool_id,
+            round,
+            w.place + 1,
+            w.winner,
+            w.ticket,
+            w.amount
+        );
+    }
+
+    // Update lottery pool winner details, then reopen recurring pools for the next round
+    lottery_pool.winners = draw_history.winners.iter().map(|w| w.winner).collect();
+
+    emit!(WinnersResolved {
+        pool_id,
+        round,
+        draw_index: draw_history.draw_index,
+        winners: draw_history.winners.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    if lottery_pool.recurring {
+        lottery_pool.start_next_round(clock.unix_timestamp)?;
+        msg!(
+            "Pool {} reopened for round {}, next draw at {}",
+            pool_id,
+            lottery_pool.round,
+            lottery_pool.draw_time
+        );
+    } else {
+        lottery_pool.status = PoolStatus::Completed;
+        ctx.accounts.global_state.release_pool()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::enums::PoolStatus;
+use crate::instructions::RevealEntropy;
+use crate::{EntropyReveal, EntropyRevealed, FortuneXError};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+pub fn reveal_entropy(ctx: Context<RevealEntropy>, pool_id: u64, value: [u8; 32]) -> Result<()> {
+    let lottery_pool = &mut ctx.accounts.lottery_pool;
+    let user_ticket = &mut ctx.accounts.user_ticket;
+    let user = &ctx.accounts.user;
+    let clock = Clock::get()?;
+
+    // Reveals only count towards a draw that has not started yet
+    require!(
+        lottery_pool.status == PoolStatus::Active || lottery_pool.status == PoolStatus::PoolFull,
+        FortuneXError::PoolNotActive
+    );
+
+    // Validate we are inside the reveal window
+    require!(
+        clock.unix_timestamp >= lottery_pool.reveal_window_start()
+            && clock.unix_timestamp < lottery_pool.draw_time,
+        FortuneXError::RevealWindowNotOpen
+    );
+
+    // Commitments are bound to the user so they cannot be copied by someone else
+    let expected = hashv(&[user.key().as_ref(), &value]).to_bytes();
+    let commitment = user_ticket
+        .commitments
+        .iter_mut()
+        .find(|c| !c.revealed && c.commitment == expected)
+        .ok_or(FortuneXError::InvalidReveal)?;
+    commitment.revealed = true;
+
+    // Mix the value into the pool entropy and keep it for the draw transcript
+    for (acc, byte) in lottery_pool.entropy.iter_mut().zip(value.iter()) {
+        *acc ^= byte;
+    }
+    lottery_pool.reveals.push(EntropyReveal {
+        user: user.key(),
+        value,
+    });
+
+    msg!(
+        "User {} revealed entropy for pool {} ({} reveals)",
+        user.key(),
+        pool_id,
+        lottery_pool.reveals.len()
+    );
+
+    emit!(EntropyRevealed {
+        pool_id,
+        round: lottery_pool.round,
+        user: user.key(),
+        value,
+        reveals: lottery_pool.reveals.len() as u64,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::instructions::SetPause;
+use crate::{FortuneXError, PauseUpdated, PAUSE_ALL};
+use anchor_lang::prelude::*;
+
+pub fn set_pause(ctx: Context<SetPause>, paused: u8) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+
+    // Validate only known operations are flagged
+    require!(paused & !PAUSE_ALL == 0, FortuneXError::InvalidPauseFlags);
+
+    // Replaces the previous flags, zero resumes every operation
+    global_state.paused = paused;
+
+    msg!(
+        "Paused operations set to {:#06b} by {}",
+        paused,
+        ctx.accounts.signer.key()
+    );
+
+    emit!(PauseUpdated {
+        paused,
+        signer: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::instructions::SetPoolPause;
+use crate::PoolPauseUpdated;
+use anchor_lang::prelude::*;
+
+pub fn set_pool_pause(ctx: Context<SetPoolPause>, pool_id: u64, paused: bool) -> Result<()> {
+    let lottery_pool = &mut ctx.accounts.lottery_pool;
+
+    if paused {
+        lottery_pool.pause()?;
+        msg!("Pool {} paused by {}", pool_id, ctx.accounts.signer.key());
+    } else {
+        lottery_pool.unpause()?;
+        msg!(
+            "Pool {} unpaused by {}, status restored to {:?}",
+            pool_id,
+            ctx.accounts.signer.key(),
+            lottery_pool.status
+        );
+    }
+
+    emit!(PoolPauseUpdated {
+        pool_id,
+        paused,
+        signer: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::enums::{PoolStatus, RandomnessSource};
+use crate::handlers::complete_draw;
+use crate::instructions::SettleDraw;
+use crate::{FortuneXError, VrfResult, PAUSE_DRAW};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+pub fn settle_draw(ctx: Context<SettleDraw>, pool_id: u64) -> Result<()> {
+    let draw = &mut ctx.accounts.draw;
+
+    // Draws stop while paused program wide or for this pool
+    draw.global_state.require_not_paused(PAUSE_DRAW)?;
+    require!(
+        draw.lottery_pool.status != PoolStatus::Paused,
+        FortuneXError::PoolPaused
+    );
+
+    // Only pools with a pending randomness request can be settled
+    require!(
+        draw.lottery_pool.status == PoolStatus::Drawing
+            && draw.lottery_pool.vrf_account != Pubkey::default(),
+        FortuneXError::DrawNotRequested
+    );
+
+    let vrf = VrfResult::load(&ctx.accounts.vrf, &draw.global_state)?;
+
+    // The result must come from the round published right after the request, later
+    // rounds were already known to whoever waited for them and need a new request
+    let settle_round = draw.lottery_pool.vrf_settle_round()?;
+    require!(
+        vrf.round >= settle_round,
+        FortuneXError::VrfRoundNotFulfilled
+    );
+    require!(vrf.round == settle_round, FortuneXError::VrfRoundMissed);
+
+    // Mix in the pool id so one VRF account can serve several pools
+    let random_seed = hashv(&[&vrf.result, &pool_id.to_le_bytes()]).to_bytes();
+
+    msg!(
+        "Settling draw for pool {} with VRF round {}",
+        pool_id,
+        vrf.round
+    );
+
+    complete_draw(
+        draw,
+        &ctx.bumps.draw,
+        pool_id,
+        random_seed,
+        RandomnessSource::Vrf {
+            account: ctx.accounts.vrf.key(),
+            round: vrf.round,
+        },
+    )
+}
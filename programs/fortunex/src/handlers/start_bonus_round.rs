@@ -0,0 +1,61 @@
+use crate::enums::PrizeSchedule;
+use crate::instructions::{MintKey, StartBonusRound};
+use crate::{BonusRoundStarted, FortuneXError, LotteryPool};
+use anchor_lang::prelude::*;
+
+pub fn start_bonus_round(
+    ctx: Context<StartBonusRound>,
+    duration: i64,
+    payout: PrizeSchedule,
+) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let bonus_round = &mut ctx.accounts.bonus_round;
+    let clock = Clock::get()?;
+
+    // Validate given duration
+    require!(duration > 0, FortuneXError::InvalidDrawInterval);
+
+    // Validate payout rules, a single jackpot or a split between several places
+    let prize_shares_bps = LotteryPool::prize_shares(&payout)?;
+
+    // Validate the bonus pool belongs to an accepted mint
+    let mint = ctx.accounts.mint.mint_key();
+    require!(
+        global_state.is_mint_accepted(&mint),
+        FortuneXError::MintNotAccepted
+    );
+
+    // Initialize bonus round
+    bonus_round.round_id = global_state.bonus_rounds_count;
+    bonus_round.start_time = clock.unix_timestamp;
+    bonus_round.end_time = clock.unix_timestamp + duration;
+    bonus_round.prize_shares_bps = prize_shares_bps;
+    bonus_round.draw_commit_slot = 0;
+    bonus_round.drawn = false;
+    bonus_round.mint = mint;
+    bonus_round.participants = Vec::new();
+    bonus_round.bump = ctx.bumps.bonus_round;
+
+    // Increment global bonus rounds count
+    global_state.bonus_rounds_count = global_state
+        .bonus_rounds_count
+        .checked_add(1)
+        .ok_or(FortuneXError::Overflow)?;
+
+    msg!(
+        "Bonus round {} started in mint {}, entries close at {}",
+        bonus_round.round_id,
+        bonus_round.mint,
+        bonus_round.end_time
+    );
+
+    emit!(BonusRoundStarted {
+        round_id: bonus_round.round_id,
+        mint: bonus_round.mint,
+        prize_shares_bps: bonus_round.prize_shares_bps.clone(),
+        start_time: bonus_round.start_time,
+        end_time: bonus_round.end_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crate::instructions::StopPool;
+use crate::{FortuneXError, PoolStopped};
+use anchor_lang::prelude::*;
+
+pub fn stop_pool(ctx: Context<StopPool>, pool_id: u64) -> Result<()> {
+    let lottery_pool = &mut ctx.accounts.lottery_pool;
+
+    // Validate the pool would otherwise keep opening new rounds
+    require!(lottery_pool.recurring, FortuneXError::PoolNotRecurring);
+
+    // The current round is drawn or refunded as usual, then the pool finishes like a
+    // one-off pool and can be closed
+    lottery_pool.recurring = false;
+
+    msg!("Pool {} stops after round {}", pool_id, lottery_pool.round);
+
+    emit!(PoolStopped {
+        pool_id,
+        round: lottery_pool.round,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::handlers::{optional_info, vault_balance, Vault};
+use crate::instructions::SweepSurplus;
+use crate::{FortuneXError, SurplusSwept};
+use anchor_lang::prelude::*;
+
+pub fn sweep_surplus(ctx: Context<SweepSurplus>, pool_id: u64) -> Result<()> {
+    let lottery_pool = &ctx.accounts.lottery_pool;
+
+    // Only what the vault holds above the prize pool and unclaimed prizes can be swept
+    let balance = vault_balance(
+        &lottery_pool.mint,
+        &ctx.accounts.vault_authority,
+        ctx.accounts.pool_token_account.as_ref(),
+    )?;
+    let surplus = balance.saturating_sub(lottery_pool.expected_vault_balance());
+    require!(surplus > 0, FortuneXError::NoSurplus);
+
+    // Create vault authority signer seeds
+    let vault_authority_seeds = &[
+        b"vault_authority".as_ref(),
+        &pool_id.to_le_bytes(),
+        &[ctx.bumps.vault_authority],
+    ];
+    let vault_signer = &[&vault_authority_seeds[..]];
+
+    let vault = Vault::new(
+        &lottery_pool.mint,
+        ctx.accounts.vault_authority.to_account_info(),
+        optional_info(&ctx.accounts.pool_token_account),
+        ctx.accounts.mint.as_ref(),
+        optional_info(&ctx.accounts.token_program),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Transfer the surplus to the platform
+    let platform_recipient = vault.recipient(
+        optional_info(&ctx.accounts.platform_wallet),
+        optional_info(&ctx.accounts.platform_token_account),
+    )?;
+    vault.pay(platform_recipient, surplus, vault_signer)?;
+
+    msg!("Swept surplus of {} from pool {}", surplus, pool_id);
+
+    emit!(SurplusSwept {
+        pool_id,
+        mint: lottery_pool.mint,
+        amount: surplus,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
+use crate::enums::CrankBounty;
 use crate::instructions::UpdateGlobalState;
-use crate::GlobalState;
+use crate::{FortuneXError, GlobalState, GlobalStateUpdated, PendingGlobalUpdate};
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
@@ -8,6 +9,12 @@ pub struct UpdateGlobalStateArgs {
     pub new_usdc_mint: Option<Pubkey>,
     pub new_platform_fee_bps: Option<u16>,
     pub new_bonus_pool_fee_bps: Option<u16>,
+    pub new_vrf_program: Option<Pubkey>,
+    pub new_vrf_account: Option<Pubkey>,
+    pub new_crank_bounty: Option<CrankBounty>,
+    pub new_max_creator_commission_bps: Option<u16>,
+    pub new_update_delay: Option<i64>,
+    pub new_guardian: Option<Pubkey>,
 }
 
 pub fn update_global_state(
@@ -15,35 +22,118 @@ pub fn update_global_state(
     args: UpdateGlobalStateArgs,
 ) -> Result<()> {
     let global_state = &mut ctx.accounts.global_state;
-
-    if let Some(new_wallet) = args.new_platform_wallet {
-        global_state.platform_wallet = new_wallet;
-    }
+    let clock = Clock::get()?;
 
     if let Some(new_usdc_mint) = args.new_usdc_mint {
+        // Validate no pool or bonus prize still holds funds in the current mint
+        let old_usdc_mint = global_state.usdc_mint;
+        let current = global_state.accepted_mint_mut(&old_usdc_mint)?;
+        require!(
+            current.escrowed == 0 && current.bonus_reserved == 0,
+            FortuneXError::MintInUse
+        );
+
+        // The new default mint needs its bonus pool, created when it was added to the
+        // accepted mints, and takes the place of the old one there
+        global_state.accepted_mint_mut(&new_usdc_mint)?.enabled = true;
+        if old_usdc_mint != new_usdc_mint {
+            global_state.accepted_mint_mut(&old_usdc_mint)?.enabled = false;
+        }
+
         global_state.usdc_mint = new_usdc_mint;
     }
 
+    if let Some(new_vrf_program) = args.new_vrf_program {
+        global_state.vrf_program = new_vrf_program;
+    }
+
+    // Pending VRF draws on a replaced account time out and are requested again
+    if let Some(new_vrf_account) = args.new_vrf_account {
+        global_state.vrf_account = new_vrf_account;
+    }
+
+    // The guardian can only pause, so replacing it is not delayed
+    if let Some(new_guardian) = args.new_guardian {
+        global_state.guardian = new_guardian;
+    }
+
     if let Some(new_platform_fee_bps) = args.new_platform_fee_bps {
         // Validate platform fee is reasonable
         GlobalState::validate_platform_fee_bps(new_platform_fee_bps)?;
-
-        global_state.platform_fee_bps = new_platform_fee_bps;
     }
 
     if let Some(new_bonus_pool_fee_bps) = args.new_bonus_pool_fee_bps {
         // Validate bonus pool fee is reasonable (max 10% = 1000 bps)
         GlobalState::validate_platform_fee_bps(new_bonus_pool_fee_bps)?;
+    }
+
+    if let Some(new_crank_bounty) = &args.new_crank_bounty {
+        // Validate crank bounty is reasonable
+        GlobalState::validate_crank_bounty(new_crank_bounty)?;
+    }
+
+    if let Some(new_max_creator_commission_bps) = args.new_max_creator_commission_bps {
+        // Validate the commission cap is reasonable
+        GlobalState::validate_max_creator_commission_bps(new_max_creator_commission_bps)?;
+    }
 
-        global_state.bonus_pool_fee_bps = new_bonus_pool_fee_bps;
+    if let Some(new_update_delay) = args.new_update_delay {
+        // Validate update delay is reasonable
+        GlobalState::validate_update_delay(new_update_delay)?;
+    }
+
+    // Fee, wallet and delay changes are queued behind the update delay and applied
+    // by execute_pending_update, replacing any update queued before
+    let update = PendingGlobalUpdate {
+        platform_wallet: args.new_platform_wallet,
+        platform_fee_bps: args.new_platform_fee_bps,
+        bonus_pool_fee_bps: args.new_bonus_pool_fee_bps,
+        crank_bounty: args.new_crank_bounty,
+        max_creator_commission_bps: args.new_max_creator_commission_bps,
+        update_delay: args.new_update_delay,
+        effective_at: clock
+            .unix_timestamp
+            .checked_add(global_state.update_delay)
+            .ok_or(FortuneXError::Overflow)?,
+    };
+    let is_queued = update.platform_wallet.is_some()
+        || update.platform_fee_bps.is_some()
+        || update.bonus_pool_fee_bps.is_some()
+        || update.crank_bounty.is_some()
+        || update.max_creator_commission_bps.is_some()
+        || update.update_delay.is_some();
+
+    if is_queued {
+        // Validate the updated fees still leave a prize for pools at the commission cap
+        let mut updated_state = (**global_state).clone();
+        updated_state.apply_update(&update)?;
+
+        msg!(
+            "Parameter update queued, effective at {}",
+            update.effective_at
+        );
+        global_state.pending_update = Some(update);
     }
 
     // Log Updated Global State
     msg!("--- Global State Updated ---");
-    msg!("Platform wallet: {}", global_state.platform_wallet);
-    msg!("USDC mint: {}", global_state.usdc_mint);
-    msg!("Platform fee: {} bps", global_state.platform_fee_bps);
-    msg!("Bonus Pool fee: {} bps", global_state.platform_fee_bps);
+    msg!("Default mint: {}", global_state.usdc_mint);
+    msg!("VRF program: {}", global_state.vrf_program);
+    msg!("VRF account: {}", global_state.vrf_account);
+    msg!("Guardian: {}", global_state.guardian);
+
+    emit!(GlobalStateUpdated {
+        usdc_mint: global_state.usdc_mint,
+        vrf_program: global_state.vrf_program,
+        vrf_account: global_state.vrf_account,
+        guardian: global_state.guardian,
+        queued_update: if is_queued {
+            global_state.pending_update.clone()
+        } else {
+            None
+        },
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
@@ -0,0 +1,75 @@
+use crate::instructions::{MintKey, UpdateMintAllowlist};
+use crate::{AcceptedMint, FortuneXError, GlobalState, MintAllowlistUpdated};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+pub fn update_mint_allowlist(ctx: Context<UpdateMintAllowlist>, is_add: bool) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    // Without a mint account the native SOL entry is updated
+    let mint_key = ctx.accounts.mint.mint_key();
+
+    if is_add {
+        // Add mint to the accepted mints, re-enabling it if it was removed before
+        require!(
+            !global_state.is_mint_accepted(&mint_key),
+            FortuneXError::MintAlreadyAccepted
+        );
+
+        if mint_key == Pubkey::default() {
+            // The native SOL bonus pool holds lamports on the bonus authority itself,
+            // funded up to its rent exempt minimum so prizes can be paid out in full
+            let rent_exempt = Rent::get()?.minimum_balance(0);
+            let top_up = rent_exempt.saturating_sub(ctx.accounts.bonus_authority.lamports());
+            if top_up > 0 {
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: ctx.accounts.bonus_authority.to_account_info(),
+                    },
+                );
+                system_program::transfer(cpi_ctx, top_up)?;
+            }
+        } else {
+            require!(
+                ctx.accounts.bonus_pool_token_account.is_some(),
+                FortuneXError::MissingTokenAccounts
+            );
+        }
+
+        if let Ok(accepted_mint) = global_state.accepted_mint_mut(&mint_key) {
+            accepted_mint.enabled = true;
+        } else {
+            require!(
+                global_state.accepted_mints.len() < GlobalState::MAX_ACCEPTED_MINTS,
+                FortuneXError::MintAllowlistFull
+            );
+
+            global_state.accepted_mints.push(AcceptedMint {
+                mint: mint_key,
+                enabled: true,
+                bonus_reserved: 0,
+                escrowed: 0,
+            });
+        }
+
+        msg!("Mint {} added to accepted mints", mint_key);
+    } else {
+        // Remove mint from the accepted mints, existing pools and bonus prizes keep working
+        require!(
+            global_state.is_mint_accepted(&mint_key),
+            FortuneXError::MintNotAccepted
+        );
+
+        global_state.accepted_mint_mut(&mint_key)?.enabled = false;
+
+        msg!("Mint {} removed from accepted mints", mint_key);
+    }
+
+    emit!(MintAllowlistUpdated {
+        mint: mint_key,
+        is_add,
+    });
+
+    Ok(())
+}
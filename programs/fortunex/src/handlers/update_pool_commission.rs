@@ -0,0 +1,40 @@
+use crate::enums::PoolStatus;
+use crate::instructions::UpdatePoolCommission;
+use crate::{FortuneXError, PoolCommissionUpdated};
+use anchor_lang::prelude::*;
+
+pub fn update_pool_commission(
+    ctx: Context<UpdatePoolCommission>,
+    pool_id: u64,
+    commission_bps: u16,
+) -> Result<()> {
+    let lottery_pool = &mut ctx.accounts.lottery_pool;
+
+    // Validate tickets are still being sold
+    require!(
+        lottery_pool.status == PoolStatus::Active || lottery_pool.status == PoolStatus::PoolFull,
+        FortuneXError::PoolNotActive
+    );
+
+    // Buyers paid expecting at most the current commission, it can only go down
+    require!(
+        commission_bps <= lottery_pool.commission_bps,
+        FortuneXError::CommissionIncrease
+    );
+
+    lottery_pool.commission_bps = commission_bps;
+
+    msg!(
+        "Creator commission of pool {} lowered to {} bps",
+        pool_id,
+        commission_bps
+    );
+
+    emit!(PoolCommissionUpdated {
+        pool_id,
+        commission_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
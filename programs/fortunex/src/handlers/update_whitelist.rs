@@ -1,5 +1,5 @@
 use crate::instructions::UpdateWhitelist;
-use crate::{FortuneXError, GlobalState};
+use crate::{FortuneXError, GlobalState, WhitelistUpdated};
 use anchor_lang::prelude::*;
 
 pub fn update_whitelist(ctx: Context<UpdateWhitelist>, is_add: bool) -> Result<()> {
@@ -34,5 +34,10 @@ pub fn update_whitelist(ctx: Context<UpdateWhitelist>, is_add: bool) -> Result<(
         }
     }
 
+    emit!(WhitelistUpdated {
+        creator: creator_key,
+        is_add,
+    });
+
     Ok(())
 }
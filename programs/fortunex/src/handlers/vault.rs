@@ -0,0 +1,166 @@
+use crate::FortuneXError;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TransferChecked};
+
+// Funds held by a program PDA. Native SOL is kept as lamports on the PDA itself,
+// any other mint in a token account owned by the PDA.
+pub enum Vault<'a, 'info> {
+    Native {
+        authority: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+    },
+    Token {
+        token_account: AccountInfo<'info>,
+        authority: AccountInfo<'info>,
+        mint: &'a InterfaceAccount<'info, Mint>,
+        token_program: AccountInfo<'info>,
+    },
+}
+
+impl<'a, 'info> Vault<'a, 'info> {
+    // Pick the vault for the given mint, the default pubkey stands for native SOL
+    // and needs none of the token accounts
+    pub fn new(
+        mint_key: &Pubkey,
+        authority: AccountInfo<'info>,
+        token_account: Option<AccountInfo<'info>>,
+        mint: Option<&'a InterfaceAccount<'info, Mint>>,
+        token_program: Option<AccountInfo<'info>>,
+        system_program: AccountInfo<'info>,
+    ) -> Result<Self> {
+        if *mint_key == Pubkey::default() {
+            return Ok(Vault::Native {
+                authority,
+                system_program,
+            });
+        }
+
+        match (token_account, mint, token_program) {
+            (Some(token_account), Some(mint), Some(token_program)) => Ok(Vault::Token {
+                token_account,
+                authority,
+                mint,
+                token_program,
+            }),
+            _ => err!(FortuneXError::MissingTokenAccounts),
+        }
+    }
+
+    // Where a payout to a user goes: their wallet for native SOL, their token account otherwise
+    pub fn recipient(
+        &self,
+        wallet: Option<AccountInfo<'info>>,
+        token_account: Option<AccountInfo<'info>>,
+    ) -> Result<AccountInfo<'info>> {
+        match self {
+            Vault::Native { .. } => wallet,
+            Vault::Token { .. } => token_account,
+        }
+        .ok_or_else(|| error!(FortuneXError::MissingTokenAccounts))
+    }
+
+    // Move funds of the signing owner (or out of their token account) into the vault
+    pub fn deposit(
+        &self,
+        owner: AccountInfo<'info>,
+        owner_token_account: Option<AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        match self {
+            Vault::Native {
+                authority,
+                system_program,
+            } => {
+                let cpi_ctx = CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: owner,
+                        to: authority.clone(),
+                    },
+                );
+                system_program::transfer(cpi_ctx, amount)
+            }
+            Vault::Token {
+                token_account,
+                mint,
+                token_program,
+                ..
+            } => {
+                let from = owner_token_account.ok_or(FortuneXError::MissingTokenAccounts)?;
+                let cpi_ctx = CpiContext::new(
+                    token_program.clone(),
+                    TransferChecked {
+                        from,
+                        mint: mint.to_account_info(),
+                        to: token_account.clone(),
+                        authority: owner,
+                    },
+                );
+                token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)
+            }
+        }
+    }
+
+    // Pay out of the vault, signed with the seeds of the vault authority PDA
+    pub fn pay(&self, to: AccountInfo<'info>, amount: u64, signer: &[&[&[u8]]]) -> Result<()> {
+        match self {
+            Vault::Native {
+                authority,
+                system_program,
+            } => {
+                let cpi_ctx = CpiContext::new_with_signer(
+                    system_program.clone(),
+                    Transfer {
+                        from: authority.clone(),
+                        to,
+                    },
+                    signer,
+                );
+                system_program::transfer(cpi_ctx, amount)
+            }
+            Vault::Token {
+                token_account,
+                authority,
+                mint,
+                token_program,
+            } => {
+                let cpi_ctx = CpiContext::new_with_signer(
+                    token_program.clone(),
+                    TransferChecked {
+                        from: token_account.clone(),
+                        mint: mint.to_account_info(),
+                        to,
+                        authority: authority.clone(),
+                    },
+                    signer,
+                );
+                token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)
+            }
+        }
+    }
+}
+
+// Account info of an account that is only passed for some mints
+pub fn optional_info<'info, T: ToAccountInfo<'info>>(
+    account: &Option<T>,
+) -> Option<AccountInfo<'info>> {
+    account.as_ref().map(|a| a.to_account_info())
+}
+
+// Funds a vault holds for its mint: the token account amount, or the lamports above
+// the rent exempt minimum of the PDA itself for native SOL
+pub fn vault_balance(
+    mint_key: &Pubkey,
+    authority: &AccountInfo,
+    token_account: Option<&InterfaceAccount<TokenAccount>>,
+) -> Result<u64> {
+    if *mint_key == Pubkey::default() {
+        return Ok(authority
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0)));
+    }
+    token_account
+        .map(|a| a.amount)
+        .ok_or_else(|| error!(FortuneXError::MissingTokenAccounts))
+}
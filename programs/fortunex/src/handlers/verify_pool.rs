@@ -0,0 +1,61 @@
+use crate::handlers::vault_balance;
+use crate::instructions::VerifyPool;
+use crate::{FortuneXError, PoolReport, UserTicket};
+use anchor_lang::prelude::*;
+
+pub fn verify_pool<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyPool<'info>>,
+    pool_id: u64,
+) -> Result<PoolReport> {
+    let lottery_pool = &ctx.accounts.lottery_pool;
+
+    let vault_balance = vault_balance(
+        &lottery_pool.mint,
+        &ctx.accounts.vault_authority,
+        ctx.accounts.pool_token_account.as_ref(),
+    )?;
+    let expected_balance = lottery_pool.expected_vault_balance();
+
+    // Add up the tickets of the given accounts, each must be a distinct account of the current round
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut user_ticket_count = 0u64;
+    let mut user_ticket_total = 0u64;
+    for account in ctx.remaining_accounts.iter() {
+        let user_ticket = Account::<UserTicket>::try_from(account)?;
+        require!(
+            user_ticket.pool == lottery_pool.key()
+                && user_ticket.round == lottery_pool.round
+                && !seen.contains(&account.key()),
+            FortuneXError::InvalidUserTicket
+        );
+        seen.push(account.key());
+
+        user_ticket_count += user_ticket.ticket_count();
+        user_ticket_total += user_ticket.amount_paid();
+    }
+
+    let report = PoolReport {
+        pool_id,
+        round: lottery_pool.round,
+        status: lottery_pool.status.clone(),
+        vault_balance,
+        expected_balance,
+        prize_pool: lottery_pool.prize_pool,
+        unclaimed_prizes: lottery_pool.unclaimed_prizes,
+        ticket_value: lottery_pool.tickets_sold * lottery_pool.ticket_price,
+        user_tickets: seen.len() as u32,
+        user_ticket_count,
+        user_ticket_total,
+        surplus: vault_balance.saturating_sub(expected_balance),
+        deficit: expected_balance.saturating_sub(vault_balance),
+    };
+
+    msg!(
+        "Pool {} vault holds {}, expected {}",
+        pool_id,
+        report.vault_balance,
+        report.expected_balance
+    );
+
+    Ok(report)
+}
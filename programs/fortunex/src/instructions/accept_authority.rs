@@ -0,0 +1,17 @@
+use crate::{FortuneXError, GlobalState, GLOBAL_STATE_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.pending_authority == new_authority.key()
+            @ FortuneXError::InvalidPendingAuthority
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    // Proposed authority, signing proves the key is usable before it takes over
+    pub new_authority: Signer<'info>,
+}
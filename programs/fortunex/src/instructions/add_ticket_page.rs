@@ -0,0 +1,31 @@
+use crate::{LotteryPool, TicketPage, LOTTERY_POOL_SEED, TICKET_PAGE_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct AddTicketPage<'info> {
+    #[account(
+        mut,
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<TicketPage>(),
+        seeds = [
+            TICKET_PAGE_SEED,
+            &pool_id.to_le_bytes(),
+            &lottery_pool.page_count.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub ticket_page: AccountLoader<'info, TicketPage>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,14 +1,15 @@
 use crate::{
-    GlobalState, LotteryPool, TicketDetails, UserTicket, GLOBAL_STATE_SEED, LOTTERY_POOL_SEED,
-    USER_TICKET_SEED, VAULT_AUTHORITY_SEED,
+    GlobalState, LotteryPool, TicketPage, UserTicket, GLOBAL_STATE_SEED, LOTTERY_POOL_SEED,
+    RANGES_PER_PAGE, TICKET_PAGE_SEED, USER_TICKET_SEED, VAULT_AUTHORITY_SEED,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 #[instruction(pool_id: u64, quantity: u64)]
 pub struct BuyTicket<'info> {
     #[account(
+        mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump
     )]
@@ -24,34 +25,59 @@ pub struct BuyTicket<'info> {
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + UserTicket::INIT_SPACE + (TicketDetails::INIT_SPACE*100),
+        space = 8 + UserTicket::INIT_SPACE,
         seeds = [
             USER_TICKET_SEED,
             user.key().as_ref(),
             &pool_id.to_le_bytes(),
+            &lottery_pool.round.to_le_bytes(),
         ],
         bump
     )]
     pub user_ticket: Account<'info, UserTicket>,
 
+    // Ticket page receiving the range of this purchase
+    #[account(
+        mut,
+        seeds = [
+            TICKET_PAGE_SEED,
+            &pool_id.to_le_bytes(),
+            &((lottery_pool.range_count / RANGES_PER_PAGE as u64) as u32).to_le_bytes(),
+        ],
+        bump
+    )]
+    pub ticket_page: AccountLoader<'info, TicketPage>,
+
     #[account(
         mut,
-        token::mint = global_state.usdc_mint,
+        token::mint = lottery_pool.mint,
         token::authority = user
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = lottery_pool.mint,
+        seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>, // this account will hold the tokens of the pool
 
+    /// CHECK: This is a PDA used as authority for the pool's token account, native SOL pools keep their lamports on it
     #[account(
         mut,
-        token::mint = global_state.usdc_mint,
         seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
         bump
     )]
-    pub pool_token_account: Account<'info, TokenAccount>, // this account will hold the tokens of the pool
+    pub vault_authority: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub user: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    // Token accounts, mint and token program are omitted for native SOL pools
+    #[account(address = lottery_pool.mint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
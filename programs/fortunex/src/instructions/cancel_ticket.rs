@@ -3,12 +3,13 @@ use crate::{
     VAULT_AUTHORITY_SEED,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 #[instruction(pool_id: u64, ticket_number: u64)]
 pub struct CancelTicket<'info> {
     #[account(
+        mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump
     )]
@@ -27,6 +28,7 @@ pub struct CancelTicket<'info> {
             USER_TICKET_SEED,
             user.key().as_ref(),
             &pool_id.to_le_bytes(),
+            &lottery_pool.round.to_le_bytes(),
         ],
         bump
     )]
@@ -34,21 +36,22 @@ pub struct CancelTicket<'info> {
 
     #[account(
         mut,
-        token::mint = global_state.usdc_mint,
+        token::mint = lottery_pool.mint,
         token::authority = user
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
-        token::mint = global_state.usdc_mint,
+        token::mint = lottery_pool.mint,
         seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
         bump
     )]
-    pub pool_token_account: Account<'info, TokenAccount>, // this account will hold the tokens of the pool
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>, // this account will hold the tokens of the pool
 
-    /// CHECK: This is a PDA used as authority for the pool's token account
+    /// CHECK: This is a PDA used as authority for the pool's token account, native SOL pools keep their lamports on it
     #[account(
+        mut,
         seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
         bump
     )]
@@ -56,14 +59,22 @@ pub struct CancelTicket<'info> {
 
     #[account(
         mut,
-        token::mint = global_state.usdc_mint,
+        token::mint = lottery_pool.mint,
         token::authority = global_state.platform_wallet
     )]
-    pub platform_token_account: Account<'info, TokenAccount>,
+    pub platform_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Platform wallet, receives the cancellation fee of native SOL pools
+    #[account(mut, address = global_state.platform_wallet)]
+    pub platform_wallet: Option<UncheckedAccount<'info>>,
 
     #[account(mut)]
     pub user: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    // Token accounts, mint and token program are omitted for native SOL pools
+    #[account(address = lottery_pool.mint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,66 @@
+use crate::{
+    BonusDrawHistory, GlobalState, BONUS_AUTHORITY_SEED, BONUS_HISTORY_SEED, GLOBAL_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct ClaimBonus<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [BONUS_HISTORY_SEED, &round_id.to_le_bytes()],
+        bump = bonus_history.bump
+    )]
+    pub bonus_history: Account<'info, BonusDrawHistory>,
+
+    // The bonus pool token account is its own authority
+    #[account(
+        mut,
+        token::mint = bonus_history.mint,
+        seeds = [BONUS_AUTHORITY_SEED, bonus_history.mint.as_ref()],
+        bump
+    )]
+    pub bonus_pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA used as authority for the bonus pool token account, the native SOL bonus pool keeps its lamports on it
+    #[account(
+        mut,
+        seeds = [BONUS_AUTHORITY_SEED, bonus_history.mint.as_ref()],
+        bump
+    )]
+    pub bonus_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Winner of the claimed place, validated against the bonus history in the handler
+    #[account(mut)]
+    pub winner: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = winner,
+        associated_token::token_program = token_program
+    )]
+    pub winner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // Token accounts, mint and token program are omitted for native SOL prizes
+    #[account(address = bonus_history.mint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    // Winner or anyone claiming on their behalf
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
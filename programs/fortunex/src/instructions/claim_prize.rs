@@ -0,0 +1,74 @@
+use crate::{
+    DrawHistory, FortuneXError, GlobalState, LotteryPool, DRAW_HISTORY_SEED, GLOBAL_STATE_SEED,
+    LOTTERY_POOL_SEED, VAULT_AUTHORITY_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64, draw_index: u64)]
+pub struct ClaimPrize<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    #[account(
+        mut,
+        seeds = [DRAW_HISTORY_SEED, &pool_id.to_le_bytes(), &draw_index.to_le_bytes()],
+        bump = draw_history.bump,
+        constraint = draw_history.version == DrawHistory::VERSION @ FortuneXError::DrawHistoryOutdated
+    )]
+    pub draw_history: Account<'info, DrawHistory>,
+
+    #[account(
+        mut,
+        token::mint = lottery_pool.mint,
+        seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA used as authority for the pool's token account, native SOL pools keep their lamports on it
+    #[account(
+        mut,
+        seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Winner of the claimed place, validated against the draw history in the handler
+    #[account(mut)]
+    pub winner: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = winner,
+        associated_token::token_program = token_program
+    )]
+    pub winner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // Token accounts, mint and token program are omitted for native SOL pools
+    #[account(address = lottery_pool.mint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    // Winner or anyone claiming on their behalf
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
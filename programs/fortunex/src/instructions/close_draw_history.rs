@@ -0,0 +1,42 @@
+use crate::{
+    DrawHistory, FortuneXError, GlobalState, DRAW_HISTORY_SEED, GLOBAL_STATE_SEED,
+    LOTTERY_POOL_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64, draw_index: u64)]
+pub struct CloseDrawHistory<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ FortuneXError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Pool that was drawn, read in the handler since close_pool may have closed it already
+    #[account(
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub lottery_pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            DRAW_HISTORY_SEED,
+            &pool_id.to_le_bytes(),
+            &draw_index.to_le_bytes(),
+        ],
+        bump = draw_history.bump
+    )]
+    pub draw_history: Account<'info, DrawHistory>,
+
+    /// CHECK: Crank that paid the rent of the record and gets it back
+    #[account(mut, address = draw_history.payer)]
+    pub payer: UncheckedAccount<'info>,
+
+    // Prunes draw records once they are indexed
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,40 @@
+use crate::{FortuneXError, LotteryPool, LOTTERY_POOL_SEED, VAULT_AUTHORITY_SEED};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct ClosePool<'info> {
+    #[account(
+        mut,
+        close = creator,
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump,
+        has_one = creator @ FortuneXError::Unauthorized
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    #[account(
+        mut,
+        token::mint = lottery_pool.mint,
+        seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA used as authority for the pool's token account, native SOL pools keep their lamports on it
+    #[account(
+        mut,
+        seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // Paid the rent of the pool and its vault and gets it back
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    // Token account and token program are omitted for native SOL pools
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
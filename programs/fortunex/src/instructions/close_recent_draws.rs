@@ -0,0 +1,27 @@
+use crate::{FortuneXError, GlobalState, RecentDraws, GLOBAL_STATE_SEED, RECENT_DRAWS_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseRecentDraws<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ FortuneXError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    // Created again by the next draw
+    #[account(
+        mut,
+        close = payer,
+        seeds = [RECENT_DRAWS_SEED],
+        bump = recent_draws.bump
+    )]
+    pub recent_draws: Account<'info, RecentDraws>,
+
+    /// CHECK: Crank that paid the rent of the list and gets it back
+    #[account(mut, address = recent_draws.payer)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,30 @@
+use crate::{UserTicket, LOTTERY_POOL_SEED, USER_TICKET_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64, round: u64)]
+pub struct CloseUserTicket<'info> {
+    /// CHECK: Pool the tickets were bought in, read in the handler since close_pool may have closed it already
+    #[account(
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub lottery_pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            USER_TICKET_SEED,
+            user.key().as_ref(),
+            &pool_id.to_le_bytes(),
+            &round.to_le_bytes(),
+        ],
+        bump = user_ticket.bump
+    )]
+    pub user_ticket: Account<'info, UserTicket>,
+
+    // Paid the rent of the ticket account and gets it back
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
@@ -0,0 +1,26 @@
+use crate::{BonusRound, GlobalState, BONUS_ROUND_SEED, GLOBAL_STATE_SEED};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct CommitBonusDraw<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [BONUS_ROUND_SEED, &round_id.to_le_bytes()],
+        bump = bonus_round.bump
+    )]
+    pub bonus_round: Account<'info, BonusRound>,
+
+    /// CHECK: SlotHashes sysvar, used to tell whether a previous commitment expired
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub crank: Signer<'info>,
+}
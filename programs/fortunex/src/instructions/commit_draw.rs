@@ -0,0 +1,27 @@
+use crate::{GlobalState, LotteryPool, GLOBAL_STATE_SEED, LOTTERY_POOL_SEED};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct CommitDraw<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    /// CHECK: SlotHashes sysvar, used to tell whether a previous commitment expired
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub crank: Signer<'info>,
+}
@@ -0,0 +1,57 @@
+use crate::{
+    BonusDrawHistory, BonusRound, GlobalState, BONUS_AUTHORITY_SEED, BONUS_HISTORY_SEED,
+    BONUS_ROUND_SEED, GLOBAL_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_spl::token_interface::TokenAccount;
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct DrawBonus<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [BONUS_ROUND_SEED, &round_id.to_le_bytes()],
+        bump = bonus_round.bump
+    )]
+    pub bonus_round: Account<'info, BonusRound>,
+
+    #[account(
+        init,
+        payer = crank,
+        space = 8 + BonusDrawHistory::INIT_SPACE,
+        seeds = [BONUS_HISTORY_SEED, &round_id.to_le_bytes()],
+        bump
+    )]
+    pub bonus_history: Account<'info, BonusDrawHistory>,
+
+    #[account(
+        token::mint = bonus_round.mint,
+        seeds = [BONUS_AUTHORITY_SEED, bonus_round.mint.as_ref()],
+        bump
+    )]
+    pub bonus_pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>, // omitted for the native SOL bonus pool
+
+    /// CHECK: This is a PDA used as authority for the bonus pool token account, the native SOL bonus pool keeps its lamports on it
+    #[account(
+        seeds = [BONUS_AUTHORITY_SEED, bonus_round.mint.as_ref()],
+        bump
+    )]
+    pub bonus_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub crank: Signer<'info>,
+
+    /// CHECK: SlotHashes sysvar, seeds the draw
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,13 +1,16 @@
 use crate::{
-    DrawHistory, GlobalState, LotteryPool, BONUS_AUTHORITY_SEED, DRAW_HISTORY_SEED, GLOBAL_STATE_SEED, LOTTERY_POOL_SEED, VAULT_AUTHORITY_SEED
+    DrawHistory, GlobalState, LotteryPool, RecentDraws, BONUS_AUTHORITY_SEED, DRAW_HISTORY_SEED,
+    GLOBAL_STATE_SEED, LOTTERY_POOL_SEED, RECENT_DRAWS_SEED, VAULT_AUTHORITY_SEED,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 #[instruction(pool_id: u64)]
 pub struct DrawWinner<'info> {
     #[account(
+        mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump
     )]
@@ -20,25 +23,40 @@ pub struct DrawWinner<'info> {
     )]
     pub lottery_pool: Account<'info, LotteryPool>,
 
+    // Every draw gets a new record under the next global draw index, so none is ever overwritten
     #[account(
-        init_if_needed,
+        init,
         payer = crank,
         space = 8 + DrawHistory::INIT_SPACE,
-        seeds = [DRAW_HISTORY_SEED, &pool_id.to_le_bytes()],
+        seeds = [
+            DRAW_HISTORY_SEED,
+            &pool_id.to_le_bytes(),
+            &global_state.draws_count.to_le_bytes(),
+        ],
         bump
     )]
     pub draw_history: Account<'info, DrawHistory>,
 
+    #[account(
+        init_if_needed,
+        payer = crank,
+        space = 8 + RecentDraws::INIT_SPACE,
+        seeds = [RECENT_DRAWS_SEED],
+        bump
+    )]
+    pub recent_draws: Account<'info, RecentDraws>,
+
     #[account(
         mut,
-        token::mint = global_state.usdc_mint,
+        token::mint = lottery_pool.mint,
         seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
         bump
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: This is a PDA used as authority for the pool's token account
+    /// CHECK: This is a PDA used as authority for the pool's token account, native SOL pools keep their lamports on it
     #[account(
+        mut,
         seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
         bump
     )]
@@ -46,31 +64,62 @@ pub struct DrawWinner<'info> {
 
     #[account(
         mut,
-        token::mint = global_state.usdc_mint,
+        token::mint = lottery_pool.mint,
         token::authority = global_state.platform_wallet
     )]
-    pub platform_token_account: Account<'info, TokenAccount>,
+    pub platform_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Platform wallet, receives the platform fee of native SOL pools
+    #[account(mut, address = global_state.platform_wallet)]
+    pub platform_wallet: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        token::mint = lottery_pool.mint,
+        seeds = [BONUS_AUTHORITY_SEED, lottery_pool.mint.as_ref()],
+        bump
+    )]
+    pub bonus_pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
+    /// CHECK: Bonus pool of the pool mint, holds the lamports of the native SOL bonus pool
     #[account(
         mut,
-        token::mint = global_state.usdc_mint,
-        seeds = [BONUS_AUTHORITY_SEED],
+        seeds = [BONUS_AUTHORITY_SEED, lottery_pool.mint.as_ref()],
         bump
     )]
-    pub bonus_pool_token_account: Account<'info, TokenAccount>,
+    pub bonus_authority: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        token::mint = global_state.usdc_mint,
+        token::mint = lottery_pool.mint,
         token::authority = lottery_pool.creator
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Pool creator, receives the commission of native SOL pools
+    #[account(mut, address = lottery_pool.creator)]
+    pub creator: Option<UncheckedAccount<'info>>,
 
+    // Receives the draw bounty
+    #[account(
+        mut,
+        token::mint = lottery_pool.mint,
+        token::authority = crank
+    )]
+    pub crank_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // Anyone can complete a draw once it is due
     #[account(mut)]
     pub crank: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: SlotHashes sysvar, mixed into the draw seed
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    // Token accounts, mint and token program are omitted for native SOL pools
+    #[account(address = lottery_pool.mint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
-    // remaining_accounts will contain all participant token accounts
-    // in the same order as lottery_pool.participants
 }
@@ -0,0 +1,26 @@
+use crate::{BonusRound, LotteryPool, UserTicket, BONUS_ROUND_SEED, LOTTERY_POOL_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct EnterBonusRound<'info> {
+    #[account(
+        mut,
+        seeds = [BONUS_ROUND_SEED, &round_id.to_le_bytes()],
+        bump = bonus_round.bump
+    )]
+    pub bonus_round: Account<'info, BonusRound>,
+
+    // Tickets of the participant, any pool and round
+    pub user_ticket: Account<'info, UserTicket>,
+
+    // Pool the tickets were bought in
+    #[account(
+        seeds = [LOTTERY_POOL_SEED, &user_ticket.pool_id.to_le_bytes()],
+        bump = lottery_pool.bump
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    // Participant or anyone entering them
+    pub payer: Signer<'info>,
+}
@@ -0,0 +1,12 @@
+use crate::{GlobalState, GLOBAL_STATE_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ExecutePendingUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
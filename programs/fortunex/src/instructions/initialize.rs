@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::{GlobalState, BONUS_AUTHORITY_SEED, GLOBAL_STATE_SEED};
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -13,21 +13,22 @@ pub struct Initialize<'info> {
     )]
     pub global_state: Account<'info, GlobalState>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
         payer = authority,
         token::mint = usdc_mint,
         token::authority = bonus_authority,
-        seeds = [BONUS_AUTHORITY_SEED],
+        token::token_program = token_program,
+        seeds = [BONUS_AUTHORITY_SEED, usdc_mint.key().as_ref()],
         bump
     )]
-    pub bonus_pool_token_account: Account<'info, TokenAccount>, // this account will hold the tokens of the pool
+    pub bonus_pool_token_account: InterfaceAccount<'info, TokenAccount>, // this account will hold the tokens of the pool
 
     /// CHECK: This is a PDA used as authority for the pool's token account
     #[account(
-        seeds = [BONUS_AUTHORITY_SEED],
+        seeds = [BONUS_AUTHORITY_SEED, usdc_mint.key().as_ref()],
         bump
     )]
     pub bonus_authority: UncheckedAccount<'info>, // this account will be the singer for transferring tokens from the bonus pool to user
@@ -38,6 +39,6 @@ pub struct Initialize<'info> {
     /// CHECK: Platform wallet for receiving fees
     pub platform_wallet: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
@@ -1,8 +1,6 @@
-use crate::{
-    GlobalState, LotteryPool, GLOBAL_STATE_SEED, LOTTERY_POOL_SEED, VAULT_AUTHORITY_SEED,
-};
+use crate::{GlobalState, LotteryPool, GLOBAL_STATE_SEED, LOTTERY_POOL_SEED, VAULT_AUTHORITY_SEED};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct InitializePool<'info> {
@@ -26,15 +24,17 @@ pub struct InitializePool<'info> {
     #[account(
         init,
         payer = authority,
-        token::mint = usdc_mint,
+        token::mint = mint,
         token::authority = vault_authority,
+        token::token_program = token_program,
         seeds = [VAULT_AUTHORITY_SEED, &global_state.pools_count.to_le_bytes()],
         bump
     )]
-    pub pool_token_account: Account<'info, TokenAccount>, // this account will hold the tokens of the pool
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>, // this account will hold the tokens of the pool, omitted for native SOL pools
 
-    /// CHECK: This is a PDA used as authority for the pool's token account
+    /// CHECK: This is a PDA used as authority for the pool's token account, native SOL pools keep their lamports on it
     #[account(
+        mut,
         seeds = [VAULT_AUTHORITY_SEED, &global_state.pools_count.to_le_bytes()],
         bump
     )]
@@ -42,17 +42,18 @@ pub struct InitializePool<'info> {
 
     #[account(
         mut,
-        token::mint = global_state.usdc_mint,
+        token::mint = mint,
         token::authority = authority
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(address = global_state.usdc_mint)]
-    pub usdc_mint: Account<'info, Mint>,
+    // Tickets of the pool are paid in this mint, it must be on the accepted mints list.
+    // Omitted for native SOL pools, which need no token accounts.
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
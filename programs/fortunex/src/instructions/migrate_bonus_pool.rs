@@ -0,0 +1,60 @@
+use crate::{FortuneXError, GlobalState, BONUS_AUTHORITY_SEED, GLOBAL_STATE_SEED};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct MigrateBonusPool<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ FortuneXError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    // Bonus pool of the first release, a single one in the default mint
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = legacy_bonus_authority,
+        token::token_program = token_program,
+        seeds = [BONUS_AUTHORITY_SEED],
+        bump
+    )]
+    pub legacy_bonus_pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA used as authority for the legacy bonus pool token account
+    #[account(
+        seeds = [BONUS_AUTHORITY_SEED],
+        bump
+    )]
+    pub legacy_bonus_authority: UncheckedAccount<'info>,
+
+    // Bonus pool of the default mint, created here if the mint was never re-added
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = mint,
+        token::authority = bonus_authority,
+        token::token_program = token_program,
+        seeds = [BONUS_AUTHORITY_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonus_pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA used as authority for the bonus pool token account
+    #[account(
+        seeds = [BONUS_AUTHORITY_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonus_authority: UncheckedAccount<'info>,
+
+    #[account(address = global_state.usdc_mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // Pays the rent of the new bonus pool and receives the old one's
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
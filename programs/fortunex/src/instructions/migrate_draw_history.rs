@@ -0,0 +1,42 @@
+use crate::{DrawHistory, GlobalState, DRAW_HISTORY_SEED, GLOBAL_STATE_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct MigrateDrawHistory<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Draw history of the first release, one per pool, read in the handler and closed
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [DRAW_HISTORY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub legacy_draw_history: UncheckedAccount<'info>,
+
+    // The record is copied under the next global draw index, like a new draw
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DrawHistory::INIT_SPACE,
+        seeds = [
+            DRAW_HISTORY_SEED,
+            &pool_id.to_le_bytes(),
+            &global_state.draws_count.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub draw_history: Account<'info, DrawHistory>,
+
+    // Anyone can migrate a record, paying the rent of the new one and receiving the old one's
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
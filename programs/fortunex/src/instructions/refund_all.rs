@@ -0,0 +1,49 @@
+use crate::{GlobalState, LotteryPool, GLOBAL_STATE_SEED, LOTTERY_POOL_SEED, VAULT_AUTHORITY_SEED};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+// Remaining accounts are (user_ticket, user_token_account) pairs, one pair per participant.
+// Native SOL pools pass the participant's wallet instead of a token account.
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct RefundAll<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    #[account(
+        mut,
+        token::mint = lottery_pool.mint,
+        seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>, // this account will hold the tokens of the pool
+
+    /// CHECK: This is a PDA used as authority for the pool's token account, native SOL pools keep their lamports on it
+    #[account(
+        mut,
+        seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // Anyone can push refunds, funds only ever go back to the ticket owners
+    pub payer: Signer<'info>,
+
+    // Token accounts, mint and token program are omitted for native SOL pools
+    #[account(address = lottery_pool.mint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
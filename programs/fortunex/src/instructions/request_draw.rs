@@ -0,0 +1,25 @@
+use crate::{GlobalState, LotteryPool, GLOBAL_STATE_SEED, LOTTERY_POOL_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct RequestDraw<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    /// CHECK: VRF result account, validated against the oracle program in the handler
+    pub vrf: UncheckedAccount<'info>,
+
+    pub crank: Signer<'info>,
+}
@@ -0,0 +1,39 @@
+use crate::{
+    DrawHistory, FortuneXError, GlobalState, LotteryPool, DRAW_HISTORY_SEED, GLOBAL_STATE_SEED,
+    LOTTERY_POOL_SEED,
+};
+use anchor_lang::prelude::*;
+
+// Remaining accounts are the ticket pages holding the winning tickets
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct ResolveWinners<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    #[account(
+        mut,
+        seeds = [
+            DRAW_HISTORY_SEED,
+            &pool_id.to_le_bytes(),
+            &lottery_pool.draw_index.to_le_bytes(),
+        ],
+        bump = draw_history.bump,
+        constraint = draw_history.version == DrawHistory::VERSION @ FortuneXError::DrawHistoryOutdated
+    )]
+    pub draw_history: Account<'info, DrawHistory>,
+
+    // Anyone can resolve the winners, the owners come from the ticket ledger
+    pub payer: Signer<'info>,
+}
@@ -0,0 +1,27 @@
+use crate::{LotteryPool, UserTicket, LOTTERY_POOL_SEED, USER_TICKET_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct RevealEntropy<'info> {
+    #[account(
+        mut,
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    #[account(
+        mut,
+        seeds = [
+            USER_TICKET_SEED,
+            user.key().as_ref(),
+            &pool_id.to_le_bytes(),
+            &lottery_pool.round.to_le_bytes(),
+        ],
+        bump = user_ticket.bump
+    )]
+    pub user_ticket: Account<'info, UserTicket>,
+
+    pub user: Signer<'info>,
+}
@@ -0,0 +1,18 @@
+use crate::{FortuneXError, GlobalState, GLOBAL_STATE_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    // Authority or guardian
+    #[account(
+        constraint = global_state.can_pause(&signer.key()) @ FortuneXError::Unauthorized
+    )]
+    pub signer: Signer<'info>,
+}
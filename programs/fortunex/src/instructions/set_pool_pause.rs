@@ -0,0 +1,25 @@
+use crate::{FortuneXError, GlobalState, LotteryPool, GLOBAL_STATE_SEED, LOTTERY_POOL_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct SetPoolPause<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    // Authority or guardian
+    #[account(
+        constraint = global_state.can_pause(&signer.key()) @ FortuneXError::Unauthorized
+    )]
+    pub signer: Signer<'info>,
+}
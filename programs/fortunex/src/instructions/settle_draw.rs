@@ -0,0 +1,16 @@
+use super::draw_winner::*;
+use crate::FortuneXError;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct SettleDraw<'info> {
+    // Same accounts as a regular draw, the payout logic is shared
+    pub draw: DrawWinner<'info>,
+
+    /// CHECK: VRF result account, must be the one recorded by request_draw
+    #[account(
+        address = draw.lottery_pool.vrf_account @ FortuneXError::InvalidVrfAccount
+    )]
+    pub vrf: UncheckedAccount<'info>,
+}
@@ -0,0 +1,32 @@
+use crate::{BonusRound, GlobalState, BONUS_ROUND_SEED, GLOBAL_STATE_SEED};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct StartBonusRound<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BonusRound::INIT_SPACE,
+        seeds = [BONUS_ROUND_SEED, &global_state.bonus_rounds_count.to_le_bytes()],
+        bump
+    )]
+    pub bonus_round: Account<'info, BonusRound>,
+
+    // The round pays out the bonus pool of this mint, it must be on the accepted mints list.
+    // Omitted for the native SOL bonus pool.
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
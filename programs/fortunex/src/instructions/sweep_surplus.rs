@@ -0,0 +1,59 @@
+use crate::{
+    FortuneXError, GlobalState, LotteryPool, GLOBAL_STATE_SEED, LOTTERY_POOL_SEED,
+    VAULT_AUTHORITY_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct SweepSurplus<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ FortuneXError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    #[account(
+        mut,
+        token::mint = lottery_pool.mint,
+        seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA used as authority for the pool's token account, native SOL pools keep their lamports on it
+    #[account(
+        mut,
+        seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = lottery_pool.mint,
+        token::authority = global_state.platform_wallet
+    )]
+    pub platform_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Platform wallet, receives the surplus of native SOL pools
+    #[account(mut, address = global_state.platform_wallet)]
+    pub platform_wallet: Option<UncheckedAccount<'info>>,
+
+    pub authority: Signer<'info>,
+
+    // Token accounts, mint and token program are omitted for native SOL pools
+    #[account(address = lottery_pool.mint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
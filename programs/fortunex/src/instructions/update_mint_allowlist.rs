@@ -0,0 +1,61 @@
+use crate::{FortuneXError, GlobalState, BONUS_AUTHORITY_SEED, GLOBAL_STATE_SEED};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct UpdateMintAllowlist<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ FortuneXError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // The mint to add/remove from the accepted mints, omitted for native SOL
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    // Bonus pool of the mint, created the first time the mint is accepted
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = mint,
+        token::authority = bonus_authority,
+        token::token_program = token_program,
+        seeds = [BONUS_AUTHORITY_SEED, mint.mint_key().as_ref()],
+        bump
+    )]
+    pub bonus_pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA used as authority for the bonus pool token account, the native SOL bonus pool keeps its lamports on it
+    #[account(
+        mut,
+        seeds = [BONUS_AUTHORITY_SEED, mint.mint_key().as_ref()],
+        bump
+    )]
+    pub bonus_authority: UncheckedAccount<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Key of the given mint, or the default pubkey standing for native SOL. Implemented for the
+// optional account and the unwrapped one, as Anchor evaluates the seeds in both forms.
+pub trait MintKey {
+    fn mint_key(&self) -> Pubkey;
+}
+
+impl MintKey for Option<InterfaceAccount<'_, Mint>> {
+    fn mint_key(&self) -> Pubkey {
+        self.as_ref().map(|mint| mint.key()).unwrap_or_default()
+    }
+}
+
+impl MintKey for InterfaceAccount<'_, Mint> {
+    fn mint_key(&self) -> Pubkey {
+        self.key()
+    }
+}
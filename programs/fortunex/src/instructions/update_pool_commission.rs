@@ -0,0 +1,16 @@
+use crate::{FortuneXError, LotteryPool, LOTTERY_POOL_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct UpdatePoolCommission<'info> {
+    #[account(
+        mut,
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump,
+        has_one = creator @ FortuneXError::Unauthorized
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    pub creator: Signer<'info>,
+}
@@ -0,0 +1,29 @@
+use crate::{LotteryPool, LOTTERY_POOL_SEED, VAULT_AUTHORITY_SEED};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+// Read only, meant to be simulated. Remaining accounts are UserTicket accounts
+// of the current round whose amounts are added up in the report.
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct VerifyPool<'info> {
+    #[account(
+        seeds = [LOTTERY_POOL_SEED, &pool_id.to_le_bytes()],
+        bump = lottery_pool.bump
+    )]
+    pub lottery_pool: Account<'info, LotteryPool>,
+
+    #[account(
+        token::mint = lottery_pool.mint,
+        seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA used as authority for the pool's token account, native SOL pools keep their lamports on it
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+}
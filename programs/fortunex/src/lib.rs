@@ -3,6 +3,7 @@ use anchor_lang::prelude::*;
 pub mod constants;
 pub mod enums;
 pub mod errors;
+pub mod events;
 pub mod handlers;
 pub mod instructions;
 pub mod state;
@@ -10,6 +11,7 @@ pub mod state;
 pub use constants::*;
 pub use enums::*;
 pub use errors::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -25,6 +27,7 @@ pub mod fortunex {
         usdc_mint: Pubkey,
         platform_fee_bps: u16,
         bonus_pool_fee_bps: u16,
+        update_delay: i64,
     ) -> Result<()> {
         handlers::initialize(
             ctx,
@@ -32,18 +35,34 @@ pub mod fortunex {
             usdc_mint,
             platform_fee_bps,
             bonus_pool_fee_bps,
+            update_delay,
         )
     }
 
     // Initialize a new lottery pool
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_pool(
         ctx: Context<InitializePool>,
         ticket_price: u64,
         min_tickets: u64,
         max_tickets: u64,
         draw_interval: i64,
+        prize_schedule: PrizeSchedule,
+        recurring: bool,
+        min_tickets_policy: MinTicketsPolicy,
+        commission_bps: u16,
     ) -> Result<()> {
-        handlers::initialize_pool(ctx, ticket_price, min_tickets, max_tickets, draw_interval)
+        handlers::initialize_pool(
+            ctx,
+            ticket_price,
+            min_tickets,
+            max_tickets,
+            draw_interval,
+            prize_schedule,
+            recurring,
+            min_tickets_policy,
+            commission_bps,
+        )
     }
 
     // Update creators whitelist
@@ -51,14 +70,73 @@ pub mod fortunex {
         handlers::update_whitelist(ctx, is_add)
     }
 
+    // Add or remove a mint pools can be created with
+    pub fn update_mint_allowlist(ctx: Context<UpdateMintAllowlist>, is_add: bool) -> Result<()> {
+        handlers::update_mint_allowlist(ctx, is_add)
+    }
+
     // Update global state
     pub fn update_global_state(ctx: Context<UpdateGlobalState>, args: handlers::UpdateGlobalStateArgs) -> Result<()> {
         handlers::update_global_state(ctx, args)
     }
 
+    // Apply a queued global state update once its delay has passed
+    pub fn execute_pending_update(ctx: Context<ExecutePendingUpdate>) -> Result<()> {
+        handlers::execute_pending_update(ctx)
+    }
+
+    // Propose a new program authority, it takes over once it accepts
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        handlers::propose_authority(ctx, new_authority)
+    }
+
+    // Accept a proposed authority transfer
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        handlers::accept_authority(ctx)
+    }
+
+    // Cancel a pending authority transfer
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        handlers::cancel_authority_transfer(ctx)
+    }
+
+    // Pause or resume operations program wide, signed by the authority or guardian
+    pub fn set_pause(ctx: Context<SetPause>, paused: u8) -> Result<()> {
+        handlers::set_pause(ctx, paused)
+    }
+
+    // Pause or resume a single pool, signed by the authority or guardian
+    pub fn set_pool_pause(ctx: Context<SetPoolPause>, pool_id: u64, paused: bool) -> Result<()> {
+        handlers::set_pool_pause(ctx, pool_id, paused)
+    }
+
+    // Lower the creator commission of a pool
+    pub fn update_pool_commission(
+        ctx: Context<UpdatePoolCommission>,
+        pool_id: u64,
+        commission_bps: u16,
+    ) -> Result<()> {
+        handlers::update_pool_commission(ctx, pool_id, commission_bps)
+    }
+
+    // Allocate the next page of the pool's ticket ledger
+    pub fn add_ticket_page(ctx: Context<AddTicketPage>, pool_id: u64) -> Result<()> {
+        handlers::add_ticket_page(ctx, pool_id)
+    }
+
     // Buy a ticket for the lottery
-    pub fn buy_ticket(ctx: Context<BuyTicket>, pool_id: u64, quantity: u64) -> Result<()> {
-        handlers::buy_ticket(ctx, pool_id, quantity)
+    pub fn buy_ticket(
+        ctx: Context<BuyTicket>,
+        pool_id: u64,
+        quantity: u64,
+        commitment: Option<[u8; 32]>,
+    ) -> Result<()> {
+        handlers::buy_ticket(ctx, pool_id, quantity, commitment)
+    }
+
+    // Reveal the entropy committed to when buying tickets
+    pub fn reveal_entropy(ctx: Context<RevealEntropy>, pool_id: u64, value: [u8; 32]) -> Result<()> {
+        handlers::reveal_entropy(ctx, pool_id, value)
     }
 
     // Cancel a ticket from the lottery
@@ -66,11 +144,142 @@ pub mod fortunex {
         handlers::cancel_ticket(ctx, pool_id, ticket_number)
     }
 
-    // Draw the winner
-    pub fn draw_winner<'info>(
-        ctx: Context<'_, '_, '_, 'info, DrawWinner<'info>>,
+    // Refund a ticket in full from a pool that did not sell enough tickets
+    pub fn refund_ticket(ctx: Context<RefundTicket>, pool_id: u64, ticket_number: u64) -> Result<()> {
+        handlers::refund_ticket(ctx, pool_id, ticket_number)
+    }
+
+    // Refund every ticket of the given participants from a refunding pool
+    pub fn refund_all<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundAll<'info>>,
         pool_id: u64,
     ) -> Result<()> {
+        handlers::refund_all(ctx, pool_id)
+    }
+
+    // Close ticket sales and commit the draw to a future slot
+    pub fn commit_draw(ctx: Context<CommitDraw>, pool_id: u64) -> Result<()> {
+        handlers::commit_draw(ctx, pool_id)
+    }
+
+    // Draw the winner
+    pub fn draw_winner(ctx: Context<DrawWinner>, pool_id: u64) -> Result<()> {
         handlers::draw_winner(ctx, pool_id)
     }
+
+    // Request VRF randomness for the draw
+    pub fn request_draw(ctx: Context<RequestDraw>, pool_id: u64) -> Result<()> {
+        handlers::request_draw(ctx, pool_id)
+    }
+
+    // Draw the winner using the fulfilled VRF result
+    pub fn settle_draw(ctx: Context<SettleDraw>, pool_id: u64) -> Result<()> {
+        handlers::settle_draw(ctx, pool_id)
+    }
+
+    // Look up the owners of the winning tickets and close the round
+    pub fn resolve_winners<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveWinners<'info>>,
+        pool_id: u64,
+    ) -> Result<()> {
+        handlers::resolve_winners(ctx, pool_id)
+    }
+
+    // Transfer the prize of a completed draw to the winner
+    pub fn claim_prize(
+        ctx: Context<ClaimPrize>,
+        pool_id: u64,
+        draw_index: u64,
+        place: u8,
+    ) -> Result<()> {
+        handlers::claim_prize(ctx, pool_id, draw_index, place)
+    }
+
+    // Close the ticket account of a drawn or refunded round, returning its rent to the user
+    pub fn close_user_ticket(
+        ctx: Context<CloseUserTicket>,
+        pool_id: u64,
+        round: u64,
+    ) -> Result<()> {
+        handlers::close_user_ticket(ctx, pool_id, round)
+    }
+
+    // Stop a recurring pool after its current round, so it finishes and can be closed
+    pub fn stop_pool(ctx: Context<StopPool>, pool_id: u64) -> Result<()> {
+        handlers::stop_pool(ctx, pool_id)
+    }
+
+    // Close a finished pool, its empty vault and its ticket pages, returning the rent to their payers
+    pub fn close_pool<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClosePool<'info>>,
+        pool_id: u64,
+    ) -> Result<()> {
+        handlers::close_pool(ctx, pool_id)
+    }
+
+    // Close the draw history of a settled round once every prize is claimed
+    pub fn close_draw_history(
+        ctx: Context<CloseDrawHistory>,
+        pool_id: u64,
+        draw_index: u64,
+    ) -> Result<()> {
+        handlers::close_draw_history(ctx, pool_id, draw_index)
+    }
+
+    // Close the recent draws list once no pool is active, the next draw creates it again
+    pub fn close_recent_draws(ctx: Context<CloseRecentDraws>) -> Result<()> {
+        handlers::close_recent_draws(ctx)
+    }
+
+    // Compare the pool vault with the pool's books, meant to be simulated
+    pub fn verify_pool<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyPool<'info>>,
+        pool_id: u64,
+    ) -> Result<PoolReport> {
+        handlers::verify_pool(ctx, pool_id)
+    }
+
+    // Move funds held by a pool vault above its prize pool and unclaimed prizes to the platform
+    pub fn sweep_surplus(ctx: Context<SweepSurplus>, pool_id: u64) -> Result<()> {
+        handlers::sweep_surplus(ctx, pool_id)
+    }
+
+    // Move the balance of the first release's bonus pool into the default mint's one
+    pub fn migrate_bonus_pool(ctx: Context<MigrateBonusPool>) -> Result<()> {
+        handlers::migrate_bonus_pool(ctx)
+    }
+
+    // Move the draw history of the first release to the current layout and draw index
+    pub fn migrate_draw_history(ctx: Context<MigrateDrawHistory>, pool_id: u64) -> Result<()> {
+        handlers::migrate_draw_history(ctx, pool_id)
+    }
+
+    // Start a bonus round among everyone buying tickets until it ends
+    pub fn start_bonus_round(
+        ctx: Context<StartBonusRound>,
+        duration: i64,
+        payout: PrizeSchedule,
+    ) -> Result<()> {
+        handlers::start_bonus_round(ctx, duration, payout)
+    }
+
+    // Enter a participant who bought tickets while the bonus round is open
+    pub fn enter_bonus_round(ctx: Context<EnterBonusRound>, round_id: u64) -> Result<()> {
+        handlers::enter_bonus_round(ctx, round_id)
+    }
+
+    // Commit the bonus draw to a future slot once the round ended
+    pub fn commit_bonus_draw(ctx: Context<CommitBonusDraw>, round_id: u64) -> Result<()> {
+        handlers::commit_bonus_draw(ctx, round_id)
+    }
+
+    // Draw the bonus round winners
+    pub fn draw_bonus(ctx: Context<DrawBonus>, round_id: u64) -> Result<()> {
+        handlers::draw_bonus(ctx, round_id)
+    }
+
+    // Transfer a bonus prize to the winner
+    pub fn claim_bonus(ctx: Context<ClaimBonus>, round_id: u64, place: u8) -> Result<()> {
+        handlers::claim_bonus(ctx, round_id, place)
+    }
 }
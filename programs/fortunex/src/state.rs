@@ -1,24 +1,42 @@
-use crate::enums::PoolStatus;
-use crate::{FortuneXError, MAX_BONUS_POOL_FEE_BPS, MAX_PLATFORM_FEE_BPS};
+use crate::enums::{CrankBounty, MinTicketsPolicy, PoolStatus, PrizeSchedule, RandomnessSource};
+use crate::{
+    FortuneXError, MAX_BONUS_POOL_FEE_BPS, MAX_CREATOR_COMMISSION_BPS, MAX_CRANK_BOUNTY_BPS,
+    MAX_PLATFORM_FEE_BPS, MAX_UPDATE_DELAY, RANGES_PER_PAGE, REVEAL_WINDOW, VRF_REQUEST_TIMEOUT,
+};
 use anchor_lang::prelude::*;
 
 // Global program state
 #[account]
 #[derive(InitSpace)]
 pub struct GlobalState {
-    pub authority: Pubkey,       // Program authority
-    pub platform_wallet: Pubkey, // Where 1% fees go
-    pub usdc_mint: Pubkey,       // USDC mint address
-    pub platform_fee_bps: u16,   // Platform fee in basis points (100 bps = 1%)
-    pub bonus_pool_fee_bps: u16, // Bonus pool fee in basis points (100 bps = 1%)
-    pub pools_count: u64,        // Total number of pools created
+    pub authority: Pubkey,               // Program authority
+    pub pending_authority: Pubkey,       // Proposed authority until it accepts (default = none)
+    pub platform_wallet: Pubkey,         // Where 1% fees go
+    pub usdc_mint: Pubkey,               // Default accepted mint
+    pub platform_fee_bps: u16,           // Platform fee in basis points (100 bps = 1%)
+    pub bonus_pool_fee_bps: u16,         // Bonus pool fee in basis points (100 bps = 1%)
+    pub crank_bounty: CrankBounty,       // Paid to whoever completes a draw
+    pub max_creator_commission_bps: u16, // Highest commission a pool creator can set
+    pub pools_count: u64,                // Total number of pools created
+    pub vrf_program: Pubkey,             // Oracle program that owns VRF result accounts
+    pub vrf_account: Pubkey,             // Only VRF result account draws can be requested on
+    pub bonus_rounds_count: u64,         // Total number of bonus rounds started
+    pub active_pools: u64,               // Pools still taking or refunding tickets
+    pub update_delay: i64,               // Seconds a queued parameter update waits before it applies
+    pub pending_update: Option<PendingGlobalUpdate>, // Queued fee and wallet changes
+    pub guardian: Pubkey,                // Can pause alongside the authority (default = none)
+    pub paused: u8,                      // Paused operations, a bitmask of the PAUSE_* flags
+    pub draws_count: u64,                // Total number of draws, indexes the draw histories
     #[max_len(100)] // Max 100 whitelisted creators
     pub creators_whitelist: Vec<Pubkey>, // Accounts allowed to create pools
+    #[max_len(10)] // Max 10 accepted mints
+    pub accepted_mints: Vec<AcceptedMint>, // Mints pools can be created with
     pub bump: u8,
 }
 
 impl GlobalState {
     pub const MAX_CREATORS: usize = 100; // Maximum number of whitelisted creators
+    pub const MAX_ACCEPTED_MINTS: usize = 10; // Maximum number of accepted mints
 
     // Check if a pubkey is in the creators whitelist
     pub fn is_creator_whitelisted(&self, creator: &Pubkey) -> bool {
@@ -49,6 +67,21 @@ impl GlobalState {
         }
     }
 
+    // Check if new pools can be created with a mint
+    pub fn is_mint_accepted(&self, mint: &Pubkey) -> bool {
+        self.accepted_mints
+            .iter()
+            .any(|m| m.mint == *mint && m.enabled)
+    }
+
+    // Allowlist entry of a mint, removed mints keep theirs for the bonus prizes still reserved
+    pub fn accepted_mint_mut(&mut self, mint: &Pubkey) -> Result<&mut AcceptedMint> {
+        self.accepted_mints
+            .iter_mut()
+            .find(|m| m.mint == *mint)
+            .ok_or(FortuneXError::MintNotAccepted.into())
+    }
+
     // Validate platform fee is reasonable
     pub fn validate_platform_fee_bps(fee_bps: u16) -> Result<()> {
         require!(
@@ -63,6 +96,132 @@ impl GlobalState {
         require!(fee_bps <= MAX_BONUS_POOL_FEE_BPS, FortuneXError::InvalidBonusPoolFee);
         Ok(())
     }
+
+    // Validate the cap on creator commissions is reasonable
+    pub fn validate_max_creator_commission_bps(commission_bps: u16) -> Result<()> {
+        require!(
+            commission_bps <= MAX_CREATOR_COMMISSION_BPS,
+            FortuneXError::InvalidCommission
+        );
+        Ok(())
+    }
+
+    // Validate platform fee, bonus pool fee, the largest crank bounty and the given
+    // commission leave something for the winners, so the prize can never underflow
+    pub fn validate_fee_total(&self, commission_bps: u16) -> Result<()> {
+        let total = self.platform_fee_bps as u64
+            + self.bonus_pool_fee_bps as u64
+            + MAX_CRANK_BOUNTY_BPS as u64
+            + commission_bps as u64;
+        require!(total < 10000, FortuneXError::InvalidFeeTotal);
+        Ok(())
+    }
+
+    // Validate crank bounty is reasonable
+    pub fn validate_crank_bounty(bounty: &CrankBounty) -> Result<()> {
+        if let CrankBounty::Bps { bps, .. } = bounty {
+            require!(
+                *bps <= MAX_CRANK_BOUNTY_BPS,
+                FortuneXError::InvalidCrankBounty
+            );
+        }
+        Ok(())
+    }
+
+    // Check if a signer can pause and unpause operations and pools
+    pub fn can_pause(&self, signer: &Pubkey) -> bool {
+        *signer == self.authority
+            || (*signer == self.guardian && self.guardian != Pubkey::default())
+    }
+
+    // Validate none of the given operations is paused
+    pub fn require_not_paused(&self, operations: u8) -> Result<()> {
+        require!(self.paused & operations == 0, FortuneXError::OperationPaused);
+        Ok(())
+    }
+
+    // Take a pool off the active count once it no longer holds ticket money
+    pub fn release_pool(&mut self) -> Result<()> {
+        self.active_pools = self
+            .active_pools
+            .checked_sub(1)
+            .ok_or(FortuneXError::Overflow)?;
+        Ok(())
+    }
+
+    // Count funds a pool takes in, they stay escrowed until paid out of its vault
+    pub fn add_escrow(&mut self, mint: &Pubkey, amount: u64) -> Result<()> {
+        let accepted_mint = self.accepted_mint_mut(mint)?;
+        accepted_mint.escrowed = accepted_mint
+            .escrowed
+            .checked_add(amount)
+            .ok_or(FortuneXError::Overflow)?;
+        Ok(())
+    }
+
+    // Release funds paid out of a pool vault, as refunds, fees or prizes
+    pub fn release_escrow(&mut self, mint: &Pubkey, amount: u64) -> Result<()> {
+        let accepted_mint = self.accepted_mint_mut(mint)?;
+        accepted_mint.escrowed = accepted_mint
+            .escrowed
+            .checked_sub(amount)
+            .ok_or(FortuneXError::Overflow)?;
+        Ok(())
+    }
+
+    // Validate update delay is reasonable
+    pub fn validate_update_delay(delay: i64) -> Result<()> {
+        require!(
+            (0..=MAX_UPDATE_DELAY).contains(&delay),
+            FortuneXError::InvalidUpdateDelay
+        );
+        Ok(())
+    }
+
+    // Apply a queued update, validating the resulting fees still leave a prize
+    // for pools at the commission cap
+    pub fn apply_update(&mut self, update: &PendingGlobalUpdate) -> Result<()> {
+        if let Some(platform_wallet) = update.platform_wallet {
+            self.platform_wallet = platform_wallet;
+        }
+        if let Some(platform_fee_bps) = update.platform_fee_bps {
+            self.platform_fee_bps = platform_fee_bps;
+        }
+        if let Some(bonus_pool_fee_bps) = update.bonus_pool_fee_bps {
+            self.bonus_pool_fee_bps = bonus_pool_fee_bps;
+        }
+        if let Some(crank_bounty) = update.crank_bounty {
+            self.crank_bounty = crank_bounty;
+        }
+        if let Some(max_creator_commission_bps) = update.max_creator_commission_bps {
+            self.max_creator_commission_bps = max_creator_commission_bps;
+        }
+        if let Some(update_delay) = update.update_delay {
+            self.update_delay = update_delay;
+        }
+        self.validate_fee_total(self.max_creator_commission_bps)
+    }
+}
+
+// Mint pools can be paid in, every accepted mint has its own bonus pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct AcceptedMint {
+    pub mint: Pubkey,        // Default pubkey for native SOL
+    pub enabled: bool,       // Whether new pools can be created with the mint
+    pub bonus_reserved: u64, // Bonus prizes drawn in the mint but not claimed yet
+    pub escrowed: u64,       // Prize pools and unclaimed prizes pools hold in the mint
+}
+
+// Fee and wallet changes waiting for their timelock to pass
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PendingGlobalUpdate {
+    pub platform_wallet: Option<Pubkey>,
+    pub platform_fee_bps: Option<u16>,
+    pub bonus_pool_fee_bps: Option<u16>,
+    pub crank_bounty: Option<CrankBounty>,
+    pub max_creator_commission_bps: Option<u16>,
+    pub update_delay: Option<i64>,
+    pub effective_at: i64, // When execute_pending_update can apply it
 }
 
 // Individual lottery pool
@@ -73,23 +232,300 @@ pub struct LotteryPool {
     pub status: PoolStatus, // Current pool status
     pub prize_pool: u64,    // Total USDC in pool
     pub ticket_price: u64,  // Price of each ticket
-    #[max_len(100)]
-    pub tickets_sold: Vec<Pubkey>, // List of tickets sold
+    pub tickets_sold: u64,  // Number of live tickets, owners are kept in ticket pages
     pub min_tickets: u64,   // Minimum number of tickets need to be sold in pool
     pub max_tickets: u64,   // Maximum number of tickets in pool
     pub draw_interval: i64, // Draw interval in seconds (e.g., 24 hours)
     pub draw_time: i64,     // Next draw timestamp
     pub created_at: i64,    // When pool was created
-    pub winner: Pubkey,     // Winner of the pool
+    #[max_len(10)]
+    pub winners: Vec<Pubkey>, // Winners of the pool, first place first
     pub commission_bps: u16, // Commission in basis points (100 bps = 1%)
     pub creator: Pubkey,    // Creator of the pool
     pub bump: u8,
     #[max_len(100)]
-    pub cancelled_tickets: Vec<u64>, // list of cancelled tickets in pool
+    pub cancelled_tickets: Vec<u64>, // Cancelled ticket numbers waiting to be reissued, sorted
+    pub vrf_account: Pubkey, // VRF account the pending draw was requested against
+    pub vrf_request_round: u64, // VRF round observed when the draw was requested
+    pub vrf_requested_at: i64, // When the pending VRF draw was requested
+    pub draw_commit_slot: u64, // Future slot whose hash seeds the draw (0 = not committed)
+    pub commitment_count: u16, // Entropy commitments made by ticket buyers
+    pub entropy: [u8; 32],  // XOR of all revealed entropy values
+    #[max_len(32)]
+    pub reveals: Vec<EntropyReveal>, // Revealed entropy, in reveal order
+    #[max_len(10)]
+    pub prize_shares_bps: Vec<u16>, // Share of the prize for each place, sums to 10000
+    pub round: u64,         // Current round, incremented after every draw of a recurring pool
+    pub recurring: bool,    // Whether the pool reopens for a new round after a draw
+    pub min_tickets_policy: MinTicketsPolicy, // Rollover or refund when min_tickets is not met
+    pub rollovers: u8,      // Times the draw of the current round was moved forward
+    pub next_ticket: u64,   // Next never issued ticket number of the current round
+    pub page_count: u32,    // Ticket pages allocated for the pool
+    pub range_count: u64,   // Ticket ranges recorded in the ticket pages this round
+    pub platform_fee_bps: u16, // Platform fee when the pool was created
+    pub bonus_pool_fee_bps: u16, // Bonus pool fee when the pool was created
+    pub crank_bounty: CrankBounty, // Crank bounty when the pool was created
+    pub mint: Pubkey,       // Token tickets are paid and prizes are paid out in, default for native SOL
+    pub paused_status: PoolStatus, // Status restored when the pool is unpaused
+    pub draw_index: u64,    // Draw history of the latest draw (from global draws_count)
+    pub participants: u64,  // Users holding tickets in the current round
+    pub unclaimed_prizes: u64, // Prizes of drawn rounds still held in the vault
 }
 
 impl LotteryPool {
-    pub const DEFAULT_COMMISSION_BPS: u16 = 0; // set no commission for the pool
+    pub const MAX_COMMITMENTS: u16 = 32; // Every commitment must fit in the reveal transcript
+    pub const MAX_WINNERS: usize = 10; // Maximum number of places in a prize schedule
+    pub const MAX_CANCELLED_TICKETS: usize = 100; // Cancelled tickets waiting to be reissued
+
+    // Turn a prize schedule into per-place shares, validating it pays out exactly 100%
+    pub fn prize_shares(schedule: &PrizeSchedule) -> Result<Vec<u16>> {
+        let shares = match schedule {
+            PrizeSchedule::Tiered { shares_bps } => shares_bps.clone(),
+            PrizeSchedule::Equal { winners } => {
+                let winners = *winners as u16;
+                require!(winners > 0, FortuneXError::InvalidPrizeSchedule);
+                // Rounding dust is paid to first place at draw time
+                vec![10000 / winners; winners as usize]
+            }
+        };
+
+        require!(
+            !shares.is_empty() && shares.len() <= Self::MAX_WINNERS,
+            FortuneXError::InvalidPrizeSchedule
+        );
+        require!(
+            shares.iter().all(|s| *s > 0),
+            FortuneXError::InvalidPrizeSchedule
+        );
+        if let PrizeSchedule::Tiered { .. } = schedule {
+            require!(
+                shares.iter().map(|s| *s as u32).sum::<u32>() == 10000,
+                FortuneXError::InvalidPrizeSchedule
+            );
+        }
+
+        Ok(shares)
+    }
+
+    // Bounty paid for drawing a pool with the given prize pool,
+    // never more than MAX_CRANK_BOUNTY_BPS of the prize pool
+    pub fn crank_bounty_amount(&self, prize_pool: u64) -> u64 {
+        let amount = match self.crank_bounty {
+            CrankBounty::Fixed { amount } => amount,
+            CrankBounty::Bps { bps, max_amount } => {
+                ((prize_pool * bps as u64) / 10000).min(max_amount)
+            }
+        };
+        amount.min((prize_pool * MAX_CRANK_BOUNTY_BPS as u64) / 10000)
+    }
+
+    // Native SOL pools keep their funds as lamports on the vault authority instead of a token account
+    pub fn is_native_sol(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+
+    // Pause the pool, keeping its status to restore when unpaused.
+    // Refunding pools cannot be paused so refunds always stay open.
+    pub fn pause(&mut self) -> Result<()> {
+        require!(self.status != PoolStatus::Paused, FortuneXError::PoolPaused);
+        require!(
+            self.status != PoolStatus::Refunding,
+            FortuneXError::PoolRefunding
+        );
+        self.paused_status = self.status.clone();
+        self.status = PoolStatus::Paused;
+        Ok(())
+    }
+
+    // Restore the status the pool had when it was paused
+    pub fn unpause(&mut self) -> Result<()> {
+        require!(
+            self.status == PoolStatus::Paused,
+            FortuneXError::PoolNotPaused
+        );
+        self.status = self.paused_status.clone();
+        Ok(())
+    }
+
+    // Whether a one-off pool is done taking and refunding tickets. Recurring pools never finish.
+    pub fn is_finished(&self) -> bool {
+        !self.recurring
+            && (self.status == PoolStatus::Completed
+                || (self.status == PoolStatus::Refunding && self.tickets_sold == 0))
+    }
+
+    // Reset the pool for the next round after a draw. Per-round accounts are keyed by
+    // round, so the tickets and draw history of the finished round stay untouched.
+    pub fn start_next_round(&mut self, now: i64) -> Result<()> {
+        self.round = self.round.checked_add(1).ok_or(FortuneXError::Overflow)?;
+        self.status = PoolStatus::Active;
+        self.rollovers = 0;
+        self.prize_pool = 0;
+        self.tickets_sold = 0;
+        self.participants = 0;
+        self.next_ticket = 0;
+        self.range_count = 0;
+        self.cancelled_tickets = Vec::new();
+
+        // Keep the original schedule, skipping draw times missed by a late draw
+        require!(self.draw_interval > 0, FortuneXError::InvalidDrawInterval);
+        let intervals = if now >= self.draw_time {
+            now.checked_sub(self.draw_time)
+                .ok_or(FortuneXError::Overflow)?
+                / self.draw_interval
+                + 1
+        } else {
+            1
+        };
+        self.draw_time = intervals
+            .checked_mul(self.draw_interval)
+            .and_then(|skipped| self.draw_time.checked_add(skipped))
+            .ok_or(FortuneXError::Overflow)?;
+
+        // Clear the randomness of the finished round
+        self.vrf_account = Pubkey::default();
+        self.vrf_request_round = 0;
+        self.vrf_requested_at = 0;
+        self.draw_commit_slot = 0;
+        self.commitment_count = 0;
+        self.entropy = [0u8; 32];
+        self.reveals = Vec::new();
+
+        Ok(())
+    }
+
+    // The VRF round a pending draw is settled with, the first one published after the request
+    pub fn vrf_settle_round(&self) -> Result<u64> {
+        self.vrf_request_round
+            .checked_add(1)
+            .ok_or(FortuneXError::Overflow.into())
+    }
+
+    // Whether a pending VRF draw went unanswered long enough to be requested again
+    pub fn is_vrf_request_expired(&self, now: i64) -> Result<bool> {
+        let expires_at = self
+            .vrf_requested_at
+            .checked_add(VRF_REQUEST_TIMEOUT)
+            .ok_or(FortuneXError::Overflow)?;
+        Ok(self.status == PoolStatus::Drawing
+            && self.vrf_account != Pubkey::default()
+            && now >= expires_at)
+    }
+
+    // Apply the min tickets policy once the draw time passed without enough tickets sold.
+    // Returns true if the draw was moved forward, false if the pool is now refunding.
+    pub fn roll_over_or_refund(&mut self, now: i64) -> Result<bool> {
+        if let MinTicketsPolicy::Rollover { max_rollovers } = self.min_tickets_policy {
+            if self.rollovers < max_rollovers {
+                self.rollovers += 1;
+                self.draw_time = now + self.draw_interval;
+                return Ok(true);
+            }
+        }
+
+        self.status = PoolStatus::Refunding;
+
+        // Nothing to refund, recurring pools can move on right away
+        if self.tickets_sold == 0 && self.recurring {
+            self.start_next_round(now)?;
+        }
+
+        Ok(false)
+    }
+
+    // Funds the vault must hold: the prize pool of a round that is not drawn yet
+    // plus the prizes of drawn rounds that were not claimed
+    pub fn expected_vault_balance(&self) -> u64 {
+        let status = if self.status == PoolStatus::Paused {
+            &self.paused_status
+        } else {
+            &self.status
+        };
+        let open_prize_pool = match status {
+            // Fees are paid out and the prizes moved to unclaimed_prizes by the draw
+            PoolStatus::Settling | PoolStatus::Completed => 0,
+            _ => self.prize_pool,
+        };
+        open_prize_pool + self.unclaimed_prizes
+    }
+
+    // Release refunded tickets from the pool. Once the last ticket is refunded
+    // recurring pools move on to the next round.
+    pub fn record_refund(&mut self, tickets: u64, amount: u64, now: i64) -> Result<()> {
+        self.tickets_sold = self
+            .tickets_sold
+            .checked_sub(tickets)
+            .ok_or(FortuneXError::Overflow)?;
+        self.prize_pool = self
+            .prize_pool
+            .checked_sub(amount)
+            .ok_or(FortuneXError::Overflow)?;
+
+        if self.tickets_sold == 0 && self.recurring {
+            self.start_next_round(now)?;
+        }
+
+        Ok(())
+    }
+
+    // Whether there is room to track another cancelled ticket until it is reissued
+    pub fn can_cancel_ticket(&self) -> bool {
+        self.cancelled_tickets.len() < Self::MAX_CANCELLED_TICKETS
+    }
+
+    // Vacate a ticket number, the next buyer is issued it again
+    pub fn cancel_ticket_number(&mut self, ticket: u64) {
+        let position = self.cancelled_tickets.partition_point(|t| *t < ticket);
+        self.cancelled_tickets.insert(position, ticket);
+    }
+
+    // Take up to quantity of the highest cancelled ticket numbers to issue again, sorted.
+    // Every run of consecutive numbers takes one free ticket range, of the buyer and of the
+    // ledger, and one is kept for the tickets that are not reissued, so scattered
+    // cancellations by others cannot use up the buyer's ranges. Numbers left over wait
+    // for the next buyer.
+    pub fn reissue_tickets(&mut self, quantity: u64, free_ranges: usize) -> Vec<u64> {
+        let mut reissued: Vec<u64> = Vec::new();
+        let mut runs = 0;
+
+        while (reissued.len() as u64) < quantity {
+            let Some(&ticket) = self.cancelled_tickets.last() else {
+                break;
+            };
+
+            // Numbers are handed out from the highest down, a gap starts a new run
+            let new_run = !matches!(reissued.last(), Some(last) if *last == ticket + 1);
+            let issues_new_range = (reissued.len() as u64 + 1) < quantity;
+            if runs + new_run as usize + issues_new_range as usize > free_ranges {
+                break;
+            }
+            runs += new_run as usize;
+
+            self.cancelled_tickets.pop();
+            reissued.push(ticket);
+        }
+
+        reissued.sort_unstable();
+        reissued
+    }
+
+    // Ticket number of the n-th live ticket, skipping cancelled tickets
+    pub fn nth_live_ticket(&self, n: u64) -> u64 {
+        let mut ticket = n;
+        for cancelled in self.cancelled_tickets.iter() {
+            if *cancelled <= ticket {
+                ticket += 1;
+            } else {
+                break;
+            }
+        }
+        ticket
+    }
+
+    // Reveals are accepted from this timestamp until draw time
+    pub fn reveal_window_start(&self) -> i64 {
+        self.draw_time - REVEAL_WINDOW.min(self.draw_interval / 2)
+    }
 }
 
 // User's ticket entry for the pool
@@ -99,20 +535,265 @@ pub struct UserTicket {
     pub user: Pubkey, // Ticket owner
     pub pool: Pubkey, // Pool this ticket belongs to
     pub pool_id: u64, // Pool ID for easier querying
-    #[max_len(100)]
-    pub tickets: Vec<TicketDetails>, // Tickets bought by user
+    pub round: u64,   // Round of the pool the tickets were bought in
+    #[max_len(30)] // MAX_TICKET_RANGES plus MAX_SPLIT_RANGES
+    pub tickets: Vec<TicketDetails>, // Ticket ranges bought by user, one per purchase
     pub bump: u8,
+    #[max_len(10)]
+    pub commitments: Vec<EntropyCommitment>, // Entropy commitments, one per purchase
 }
 
 impl UserTicket {
-    pub const MAX_TICKETS_PER_USER: usize = 1; // One ticket per user per pool
+    pub const MAX_TICKET_RANGES: usize = 20; // Ranges a purchase can leave the user with
+    pub const MAX_SPLIT_RANGES: usize = 10; // Kept free for cancels splitting a range in two
+    pub const MAX_COMMITMENTS_PER_USER: usize = 10;
+
+    // Number of tickets held across all ranges
+    pub fn ticket_count(&self) -> u64 {
+        self.tickets.iter().map(|t| t.count).sum()
+    }
+
+    // Total amount paid for the tickets held
+    pub fn amount_paid(&self) -> u64 {
+        self.tickets.iter().map(|t| t.count * t.amount_paid).sum()
+    }
+
+    // Add tickets start_ticket..start_ticket + count, extending the last range when the
+    // tickets follow it within the same purchase
+    pub fn add_tickets(
+        &mut self,
+        start_ticket: u64,
+        count: u64,
+        amount_paid: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        if let Some(last) = self.tickets.last_mut() {
+            if last.end_ticket() == start_ticket
+                && last.amount_paid == amount_paid
+                && last.timestamp == timestamp
+            {
+                last.count += count;
+                return Ok(());
+            }
+        }
+
+        require!(
+            self.tickets.len() < Self::MAX_TICKET_RANGES,
+            FortuneXError::TooManyTicketRanges
+        );
+        self.tickets.push(TicketDetails {
+            start_ticket,
+            count,
+            amount_paid,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    // Remove a single ticket from the range holding it, returning the amount paid for it
+    pub fn remove_ticket(&mut self, ticket_number: u64) -> Result<u64> {
+        let Some(index) = self
+            .tickets
+            .iter()
+            .position(|t| t.start_ticket <= ticket_number && ticket_number < t.end_ticket())
+        else {
+            msg!("User does not own ticket {}", ticket_number);
+            return err!(FortuneXError::TicketNotFound);
+        };
+
+        let range = self.tickets[index].clone();
+        let before = ticket_number - range.start_ticket;
+        let after = range.end_ticket() - ticket_number - 1;
+
+        match (before, after) {
+            (0, 0) => {
+                self.tickets.remove(index);
+            }
+            (0, _) => {
+                self.tickets[index].start_ticket += 1;
+                self.tickets[index].count -= 1;
+            }
+            (_, 0) => self.tickets[index].count -= 1,
+            _ => {
+                // Purchases stop at MAX_TICKET_RANGES, so splits always have room
+                // for at least MAX_SPLIT_RANGES cancels after the last purchase
+                require!(
+                    self.tickets.len() < Self::MAX_TICKET_RANGES + Self::MAX_SPLIT_RANGES,
+                    FortuneXError::TooManyTicketRanges
+                );
+                self.tickets[index].count = before;
+                self.tickets.insert(
+                    index + 1,
+                    TicketDetails {
+                        start_ticket: ticket_number + 1,
+                        count: after,
+                        ..range.clone()
+                    },
+                );
+            }
+        }
+
+        Ok(range.amount_paid)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct EntropyCommitment {
+    pub commitment: [u8; 32], // hash(user || value)
+    pub revealed: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct EntropyReveal {
+    pub user: Pubkey,    // Participant who revealed
+    pub value: [u8; 32], // Revealed entropy value
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct TicketDetails {
-    pub ticket_number: u64, // Single ticket number (1-10)
-    pub amount_paid: u64,   // USDC paid ($10)
-    pub timestamp: i64,     // When ticket was bought
+    pub start_ticket: u64, // First ticket number of the range
+    pub count: u64,        // Number of tickets in the range
+    pub amount_paid: u64,  // USDC paid per ticket
+    pub timestamp: i64,    // When the tickets were bought
+}
+
+impl TicketDetails {
+    // Ticket number following the last ticket of the range
+    pub fn end_ticket(&self) -> u64 {
+        self.start_ticket + self.count
+    }
+}
+
+// Tickets start_ticket..start_ticket + count bought by owner in a single purchase
+#[zero_copy]
+pub struct TicketRange {
+    pub start_ticket: u64, // First ticket number of the range
+    pub count: u64,        // Number of tickets in the range
+    pub owner: Pubkey,     // Buyer of the tickets
+}
+
+// Page of the ticket ledger of a pool. Purchases are recorded in order as ranges,
+// range n of a round lives in ranges[n % RANGES_PER_PAGE] of page n / RANGES_PER_PAGE.
+// Reissued ticket numbers get a range of their own, the latest range holding a ticket
+// names its owner. Pages are reused by every round of a recurring pool.
+#[account(zero_copy)]
+pub struct TicketPage {
+    pub pool: Pubkey,                           // Pool this page belongs to
+    pub payer: Pubkey,                          // Paid the rent, refunded when the pool closes
+    pub page_index: u32,                        // Position of the page in the ledger
+    pub _padding: [u8; 4],                      // Aligns the ranges
+    pub ranges: [TicketRange; RANGES_PER_PAGE], // Ticket ranges of the page
+}
+
+// Ticket pages of a pool, passed to an instruction as remaining accounts
+pub struct TicketLedger<'info> {
+    pages: Vec<AccountLoader<'info, TicketPage>>,
+    range_count: u64,
+}
+
+impl<'info> TicketLedger<'info> {
+    // Load the given accounts, checking every one is a ticket page of the pool and every
+    // page holding ranges of the round is there, since a later range can override an earlier one
+    pub fn load(
+        accounts: &'info [AccountInfo<'info>],
+        pool: &Pubkey,
+        range_count: u64,
+    ) -> Result<Self> {
+        let mut pages = Vec::with_capacity(accounts.len());
+        let mut loaded = vec![false; range_count.div_ceil(RANGES_PER_PAGE as u64) as usize];
+        for account in accounts {
+            let page = AccountLoader::<TicketPage>::try_from(account)?;
+            {
+                let page = page.load()?;
+                require!(page.pool == *pool, FortuneXError::InvalidTicketPage);
+                if let Some(loaded) = loaded.get_mut(page.page_index as usize) {
+                    *loaded = true;
+                }
+            }
+            pages.push(page);
+        }
+        require!(loaded.iter().all(|l| *l), FortuneXError::TicketPageMissing);
+        Ok(Self { pages, range_count })
+    }
+
+    // Owner of the given ticket number, named by the latest range holding it
+    pub fn owner(&self, ticket: u64) -> Result<Pubkey> {
+        let mut latest: Option<(u64, Pubkey)> = None;
+        for page in self.pages.iter() {
+            let page = page.load()?;
+
+            // Ranges past the range count are left over from a previous round
+            let first_range = page.page_index as u64 * RANGES_PER_PAGE as u64;
+            if first_range >= self.range_count {
+                continue;
+            }
+            let len = (self.range_count - first_range).min(RANGES_PER_PAGE as u64) as usize;
+
+            if let Some(index) = page.ranges[..len]
+                .iter()
+                .rposition(|r| r.start_ticket <= ticket && ticket < r.start_ticket + r.count)
+            {
+                let range = first_range + index as u64;
+                if !matches!(latest, Some((latest_range, _)) if latest_range > range) {
+                    latest = Some((range, page.ranges[index].owner));
+                }
+            }
+        }
+        match latest {
+            Some((_, owner)) => Ok(owner),
+            None => {
+                msg!("Missing ticket page for ticket #{}", ticket);
+                err!(FortuneXError::TicketPageMissing)
+            }
+        }
+    }
+}
+
+// Bonus draw among everyone who bought a ticket in any pool between start_time and end_time.
+// Participants are entered from their UserTicket accounts while the round is open.
+#[account]
+#[derive(InitSpace)]
+pub struct BonusRound {
+    pub round_id: u64,   // Unique bonus round ID (from global bonus_rounds_count)
+    pub start_time: i64, // Tickets bought from this timestamp are eligible
+    pub end_time: i64,   // Entries close and the draw can be committed at this timestamp
+    #[max_len(10)]
+    pub prize_shares_bps: Vec<u16>, // Share of the bonus pool for each place, sums to 10000
+    pub draw_commit_slot: u64, // Future slot whose hash seeds the draw (0 = not committed)
+    pub drawn: bool,     // Whether the winners have been drawn
+    pub mint: Pubkey,    // Mint whose bonus pool the round pays out
+    #[max_len(250)]
+    pub participants: Vec<Pubkey>, // Eligible participants, one entry each
+    pub bump: u8,
+}
+
+impl BonusRound {
+    pub const MAX_PARTICIPANTS: usize = 250; // Keeps the account under the 10KB account creation limit
+}
+
+// Result of a bonus draw
+#[account]
+#[derive(InitSpace)]
+pub struct BonusDrawHistory {
+    pub round_id: u64,           // Bonus round ID
+    pub prize_amount: u64,       // Bonus pool amount distributed to the winners
+    pub total_participants: u64, // Participants entered in the round
+    pub draw_timestamp: i64,     // When draw occurred
+    pub random_seed: [u8; 32],   // Random seed used
+    pub slot_hash_slot: u64,     // Slot whose hash seeded the draw
+    pub slot_hash: [u8; 32],     // SlotHashes entry that seeded the draw
+    pub mint: Pubkey,            // Mint the prizes are paid in
+    #[max_len(10)]
+    pub winners: Vec<BonusWinner>, // One entry per place, first place first
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct BonusWinner {
+    pub place: u8,      // 0 = first place
+    pub winner: Pubkey, // Participant drawn for this place
+    pub amount: u64,    // Prize for this place
+    pub claimed: bool,  // Whether the prize was claimed
 }
 
 // Pool's token account to hold USDC
@@ -125,13 +806,70 @@ pub struct PoolVault {
     pub bump: u8,
 }
 
+// Result of verify_pool, compares what the pool vault holds with the pool's books
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolReport {
+    pub pool_id: u64,
+    pub round: u64,
+    pub status: PoolStatus,
+    pub vault_balance: u64,     // Held by the vault, lamports above rent for native SOL pools
+    pub expected_balance: u64,  // Prize pool of an undrawn round plus unclaimed prizes
+    pub prize_pool: u64,        // Prize pool of the current round
+    pub unclaimed_prizes: u64,  // Prizes of drawn rounds not claimed yet
+    pub ticket_value: u64,      // Live tickets of the current round at the ticket price
+    pub user_tickets: u32,      // UserTicket accounts passed as remaining accounts
+    pub user_ticket_count: u64, // Live tickets held by those accounts
+    pub user_ticket_total: u64, // Amount paid for those tickets
+    pub surplus: u64,           // Held above the expected balance, can be swept
+    pub deficit: u64,           // Missing from the expected balance
+}
+
 // Draw history for transparency
 #[account]
 #[derive(InitSpace)]
 pub struct DrawHistory {
     pub pool: Pubkey,          // Pool this draw belongs to
     pub pool_id: u64,          // Pool ID
-    pub winner: Pubkey,        // Winner address
+    pub round: u64,            // Round of the pool that was drawn
+    pub draw_index: u64,       // Global draw index the record is stored under
+    pub prize_amount: u64,     // Total amount won, split between winners
+    pub total_tickets: u64,    // Total tickets in draw
+    pub draw_timestamp: i64,   // When draw occurred
+    pub random_seed: [u8; 32], // Random seed used
+    pub bump: u8,
+    pub slot_hash_slot: u64,   // Slot whose hash was mixed into the seed
+    pub slot_hash: [u8; 32],   // SlotHashes entry mixed into the seed
+    #[max_len(32)]
+    pub reveals: Vec<EntropyReveal>, // Revealed entropy mixed into the seed
+    #[max_len(10)]
+    pub winners: Vec<PrizeWinner>, // Every place of the prize schedule
+    // Added in version 1, migrated records of the first release leave the fees zero
+    pub version: u8,               // Layout version of the record
+    pub total_prize: u64,          // Prize pool the fees and prizes were taken from
+    pub platform_fee: u64,         // Paid to the platform wallet
+    pub bonus_pool_fee: u64,       // Paid to the bonus pool of the mint
+    pub commission: u64,           // Paid to the pool creator
+    pub crank_bounty: u64,         // Paid to whoever completed the draw
+    pub platform_fee_bps: u16,     // Fees in effect for the draw
+    pub bonus_pool_fee_bps: u16,
+    pub commission_bps: u16,
+    pub ticket_price: u64,         // Ticket price of the drawn round
+    pub participants: u64,         // Users holding tickets in the draw
+    pub randomness: RandomnessSource, // Where the random seed came from
+    pub payer: Pubkey,                // Paid the rent, gets it back when the record is closed
+}
+
+impl DrawHistory {
+    pub const VERSION: u8 = 1; // Current layout version, written by every new draw
+}
+
+// Draw history as written by the first release, a single record per pool under
+// [DRAW_HISTORY_SEED, pool_id]. Only read by migrate_draw_history.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyDrawHistory {
+    pub pool: Pubkey,          // Pool this draw belongs to
+    pub pool_id: u64,          // Pool ID
+    pub winner: Pubkey,        // Winner address, paid during the draw
     pub prize_amount: u64,     // Amount won
     pub total_tickets: u64,    // Total tickets in draw
     pub draw_timestamp: i64,   // When draw occurred
@@ -139,3 +877,81 @@ pub struct DrawHistory {
     pub random_seed: [u8; 32], // Random seed used
     pub bump: u8,
 }
+
+// Latest draws across all pools, so past draws can be listed from a single account.
+// Once full every new draw replaces the oldest entry.
+#[account]
+#[derive(InitSpace)]
+pub struct RecentDraws {
+    pub head: u32, // Entry replaced by the next draw once full
+    #[max_len(32)]
+    pub draws: Vec<DrawSummary>, // Unordered, sort by draw_index
+    pub bump: u8,
+    pub payer: Pubkey, // Crank that created the list, gets the rent back when it is closed
+}
+
+impl RecentDraws {
+    pub const MAX_DRAWS: usize = 32; // Draws kept in the ring buffer
+
+    // Record a draw, replacing the oldest entry once the buffer is full
+    pub fn record(&mut self, summary: DrawSummary) {
+        if self.draws.len() < Self::MAX_DRAWS {
+            self.draws.push(summary);
+        } else {
+            self.draws[self.head as usize] = summary;
+            self.head = (self.head + 1) % Self::MAX_DRAWS as u32;
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct DrawSummary {
+    pub draw_index: u64,     // Global draw index, locates the full DrawHistory
+    pub pool_id: u64,        // Pool that was drawn
+    pub round: u64,          // Round of the pool that was drawn
+    pub mint: Pubkey,        // Mint the prizes are paid in
+    pub prize_amount: u64,   // Total amount won, split between winners
+    pub total_tickets: u64,  // Total tickets in draw
+    pub draw_timestamp: i64, // When draw occurred
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PrizeWinner {
+    pub place: u8,       // 0 = first place
+    pub ticket: u64,     // Winning ticket number
+    pub winner: Pubkey,  // Owner of the winning ticket
+    pub amount: u64,     // Prize for this place
+    pub claimed: bool,   // Whether the prize was claimed
+}
+
+// Randomness published by the VRF oracle (Switchboard-style). The oracle writes a new
+// result for every round, so a draw is settled with the round right after its request.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VrfResult {
+    pub authority: Pubkey,   // Account allowed to request rounds
+    pub round: u64,          // Incremented every time a result is published
+    pub result: [u8; 32],    // Randomness for the current round
+    pub fulfilled_slot: u64, // Slot at which the current round was fulfilled
+}
+
+impl VrfResult {
+    // Read the result account, checking that it is the account pinned in global state
+    // and owned by the configured oracle program, so callers cannot bring their own results
+    pub fn load(vrf_account: &AccountInfo, global_state: &GlobalState) -> Result<Self> {
+        require!(
+            global_state.vrf_program != Pubkey::default()
+                && global_state.vrf_account != Pubkey::default(),
+            FortuneXError::VrfNotConfigured
+        );
+        require!(
+            vrf_account.key() == global_state.vrf_account
+                && *vrf_account.owner == global_state.vrf_program,
+            FortuneXError::InvalidVrfAccount
+        );
+
+        let data = vrf_account.try_borrow_data()?;
+        // Skip the 8 byte account discriminator written by the oracle program
+        let mut body = data.get(8..).ok_or(FortuneXError::InvalidVrfAccount)?;
+        VrfResult::deserialize(&mut body).map_err(|_| FortuneXError::InvalidVrfAccount.into())
+    }
+}
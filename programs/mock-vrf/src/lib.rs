@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+declare_id!("bJcT9bUdwAgAxF8fLQDWU3f3uxpBW8CQq7qGExveoVj");
+
+// Minimal stand-in for a Switchboard-style VRF oracle. The authority publishes
+// a new result per round, which fortunex reads through its VrfResult layout.
+#[program]
+pub mod mock_vrf {
+    use super::*;
+
+    pub fn initialize(ctx: Context<InitializeVrf>) -> Result<()> {
+        let vrf = &mut ctx.accounts.vrf;
+        vrf.authority = ctx.accounts.authority.key();
+        vrf.round = 0;
+        vrf.result = [0u8; 32];
+        vrf.fulfilled_slot = 0;
+        Ok(())
+    }
+
+    // Publish the randomness for the next round
+    pub fn fulfill(ctx: Context<Fulfill>, result: [u8; 32]) -> Result<()> {
+        let vrf = &mut ctx.accounts.vrf;
+        vrf.round += 1;
+        vrf.result = result;
+        vrf.fulfilled_slot = Clock::get()?.slot;
+
+        msg!("VRF round {} fulfilled", vrf.round);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeVrf<'info> {
+    #[account(init, payer = authority, space = 8 + VrfState::INIT_SPACE)]
+    pub vrf: Account<'info, VrfState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Fulfill<'info> {
+    #[account(mut, has_one = authority)]
+    pub vrf: Account<'info, VrfState>,
+
+    pub authority: Signer<'info>,
+}
+
+// Layout must match fortunex::VrfResult
+#[account]
+#[derive(InitSpace)]
+pub struct VrfState {
+    pub authority: Pubkey,
+    pub round: u64,
+    pub result: [u8; 32],
+    pub fulfilled_slot: u64,
+}
@@ -1,19 +1,35 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Fortunex } from "../target/types/fortunex";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { MockVrf } from "../target/types/mock_vrf";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_SLOT_HASHES_PUBKEY,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  ExtensionType,
   createMint,
   createAccount,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getMintLen,
   mintTo,
   getAssociatedTokenAddress,
 } from "@solana/spl-token";
 import { assert } from "chai";
+import { createHash } from "crypto";
 
 describe("fortunex", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
   const program = anchor.workspace.fortunex as Program<Fortunex>;
+  const vrfProgram = anchor.workspace.mockVrf as Program<MockVrf>;
   const provider = anchor.getProvider();
 
   // Seeds
@@ -22,12 +38,352 @@ describe("fortunex", () => {
   const VAULT_AUTHORITY_SEED = "vault_authority";
   const BONUS_AUTHORITY_SEED = "bonus_authority";
   const USER_TICKET_SEED = "user_ticket";
+  const TICKET_PAGE_SEED = "ticket_page";
+  const RANGES_PER_PAGE = 200;
 
   // keypairs
   const authority = Keypair.generate();
   const platformWallet = Keypair.generate();
   let usdcMint: any;
   let creatorTokenAccount;
+  let crankTokenAccount;
+
+  const [globalStatePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_STATE_SEED)],
+    program.programId
+  );
+  // Bonus pool of a mint, it is its own authority
+  const bonusTokenAccountFor = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from(BONUS_AUTHORITY_SEED), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  // PDAs of a pool
+  const poolAccounts = (poolId: number) => {
+    const poolIdBytes = new anchor.BN(poolId).toArrayLike(Buffer, "le", 8);
+    const [lotteryPool] = PublicKey.findProgramAddressSync(
+      [Buffer.from(LOTTERY_POOL_SEED), poolIdBytes],
+      program.programId
+    );
+    const [vaultAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from(VAULT_AUTHORITY_SEED), poolIdBytes],
+      program.programId
+    );
+    return {
+      lotteryPool,
+      vaultAuthority,
+      poolTokenAccount: vaultAuthority,
+    };
+  };
+
+  // Draw history stored under the given global draw index
+  const drawHistoryAccount = (poolId: number, drawIndex: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("draw_history"),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+        new anchor.BN(drawIndex).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+
+  // Draw history the next draw of any pool is recorded in
+  const nextDrawHistory = async (poolId: number) => {
+    const { drawsCount } = await program.account.globalState.fetch(globalStatePda);
+    return drawHistoryAccount(poolId, drawsCount.toNumber());
+  };
+
+  // Draw history of the latest draw of a pool
+  const latestDrawHistory = async (poolId: number) => {
+    const { drawIndex } = await program.account.lotteryPool.fetch(
+      poolAccounts(poolId).lotteryPool
+    );
+    return drawHistoryAccount(poolId, drawIndex.toNumber());
+  };
+
+  // Create a new pool from the authority and return its id
+  const createPool = async (
+    minTickets: number,
+    maxTickets: number,
+    drawInterval: number,
+    prizeSchedule: object = { equal: { winners: 1 } },
+    recurring = false,
+    minTicketsPolicy: object = { rollover: { maxRollovers: 3 } },
+    commissionBps = 0
+  ): Promise<number> => {
+    const globalState = await program.account.globalState.fetch(globalStatePda);
+    const poolId = globalState.poolsCount.toNumber();
+    const pool = poolAccounts(poolId);
+
+    await program.methods
+      .initializePool(
+        new anchor.BN(10_000_000),
+        new anchor.BN(minTickets),
+        new anchor.BN(maxTickets),
+        new anchor.BN(drawInterval),
+        prizeSchedule,
+        recurring,
+        minTicketsPolicy,
+        commissionBps
+      )
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        mint: usdcMint,
+        creatorTokenAccount: creatorTokenAccount,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await addTicketPage(poolId, authority);
+
+    return poolId;
+  };
+
+  // PDA of a page of a pool's ticket ledger
+  const ticketPage = (poolId: number, index: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(TICKET_PAGE_SEED),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+        new anchor.BN(index).toArrayLike(Buffer, "le", 4),
+      ],
+      program.programId
+    )[0];
+
+  // Remaining accounts for every ticket page of the pool
+  const ticketPageMetas = async (poolId: number) => {
+    const pool = await program.account.lotteryPool.fetch(
+      poolAccounts(poolId).lotteryPool
+    );
+    return [...Array(pool.pageCount).keys()].map((index) => ({
+      pubkey: ticketPage(poolId, index),
+      isWritable: false,
+      isSigner: false,
+    }));
+  };
+
+  // Ticket page receiving the range of the next purchase
+  const currentTicketPage = async (poolId: number) => {
+    const pool = await program.account.lotteryPool.fetch(
+      poolAccounts(poolId).lotteryPool
+    );
+    return ticketPage(
+      poolId,
+      Math.floor(pool.rangeCount.toNumber() / RANGES_PER_PAGE)
+    );
+  };
+
+  const addTicketPage = async (poolId: number, payer: Keypair) => {
+    const pool = await program.account.lotteryPool.fetch(
+      poolAccounts(poolId).lotteryPool
+    );
+    await program.methods
+      .addTicketPage(new anchor.BN(poolId))
+      .accounts({
+        lotteryPool: poolAccounts(poolId).lotteryPool,
+        ticketPage: ticketPage(poolId, pool.pageCount),
+        payer: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+  };
+
+  // Close a finished pool together with its ticket pages, each page's rent goes back
+  // to whoever paid for it
+  const closePool = async (poolId: number, withTicketPages = true) => {
+    const pool = poolAccounts(poolId);
+    const pages = withTicketPages ? await ticketPageMetas(poolId) : [];
+    const remainingAccounts = [];
+    for (const page of pages) {
+      const { payer } = await program.account.ticketPage.fetch(page.pubkey);
+      remainingAccounts.push(
+        { pubkey: page.pubkey, isWritable: true, isSigner: false },
+        { pubkey: payer, isWritable: true, isSigner: false }
+      );
+    }
+
+    await program.methods
+      .closePool(new anchor.BN(poolId))
+      .accounts({
+        lotteryPool: pool.lotteryPool,
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([authority])
+      .rpc();
+    return pages.map((page) => page.pubkey);
+  };
+
+  // Look up the owners of the winning tickets of the round being settled
+  const resolveWinners = async (poolId: number) => {
+    const drawHistory = await latestDrawHistory(poolId);
+
+    return program.methods
+      .resolveWinners(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: poolAccounts(poolId).lotteryPool,
+        drawHistory,
+        payer: authority.publicKey,
+      })
+      .remainingAccounts(await ticketPageMetas(poolId))
+      .signers([authority])
+      .rpc();
+  };
+
+  // Fund a fresh participant with SOL and USDC held in their ATA
+  const createParticipant = async (usdcAmount: number) => {
+    const user = Keypair.generate();
+    await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await new Promise((resolve) => setTimeout(resolve, 500));
+
+    const tokenAccount = await createAccount(
+      provider.connection,
+      user,
+      usdcMint,
+      user.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      authority,
+      usdcMint,
+      tokenAccount,
+      authority.publicKey,
+      usdcAmount
+    );
+
+    return { user, tokenAccount };
+  };
+
+  const userTicketAccount = (user: PublicKey, poolId: number, round = 0) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(USER_TICKET_SEED),
+        user.toBuffer(),
+        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+        new anchor.BN(round).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+
+  const buyTickets = async (
+    participant: { user: Keypair; tokenAccount: PublicKey },
+    poolId: number,
+    quantity: number,
+    commitment: number[] | null = null,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+  ) => {
+    const pool = poolAccounts(poolId);
+    const { round, mint } = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    return program.methods
+      .buyTicket(new anchor.BN(poolId), new anchor.BN(quantity), commitment)
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        userTicket: userTicketAccount(participant.user.publicKey, poolId, round.toNumber()),
+        ticketPage: await currentTicketPage(poolId),
+        userTokenAccount: participant.tokenAccount,
+        poolTokenAccount: pool.poolTokenAccount,
+        user: participant.user.publicKey,
+        mint,
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([participant.user])
+      .rpc();
+  };
+
+  // Close sales and wait until the committed slot has been produced
+  const commitDraw = async (poolId: number, crank: Keypair = authority) => {
+    await program.methods
+      .commitDraw(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: poolAccounts(poolId).lotteryPool,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        crank: crank.publicKey,
+      })
+      .signers([crank])
+      .rpc();
+
+    const pool = await program.account.lotteryPool.fetch(
+      poolAccounts(poolId).lotteryPool
+    );
+    while ((await provider.connection.getSlot()) <= pool.drawCommitSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+  };
+
+  // Claim the prize of one place of a draw into the winner's ATA, paid by `payer`.
+  // Defaults to the latest draw of the pool.
+  const claimPrize = async (
+    poolId: number,
+    payer: Keypair,
+    place = 0,
+    drawIndex: number | null = null
+  ) => {
+    const pool = poolAccounts(poolId);
+    if (drawIndex === null) {
+      const poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+      drawIndex = poolState.drawIndex.toNumber();
+    }
+    const drawHistoryPda = drawHistoryAccount(poolId, drawIndex);
+    const drawHistory = await program.account.drawHistory.fetch(drawHistoryPda);
+    const winner = drawHistory.winners[place].winner;
+    const winnerTokenAccount = await getAssociatedTokenAddress(usdcMint, winner);
+
+    const signature = await program.methods
+      .claimPrize(new anchor.BN(poolId), new anchor.BN(drawIndex), place)
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        drawHistory: drawHistoryPda,
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        winner: winner,
+        winnerTokenAccount: winnerTokenAccount,
+        mint: usdcMint,
+        payer: payer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+
+    return { drawHistory, winnerTokenAccount, signature };
+  };
+
+  // Decode the events logged by a confirmed transaction
+  const eventsOf = async (signature: string) => {
+    await provider.connection.confirmTransaction(signature, "confirmed");
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    return Array.from(parser.parseLogs(tx.meta.logMessages));
+  };
+
+  // Block until the given unix timestamp (seconds) has passed on the validator
+  const waitUntil = (timestamp: number) => {
+    console.log(`Waiting until ${new Date(timestamp * 1000).toISOString()}....`);
+    while (Date.now() < timestamp * 1000 + 2000) { }
+  };
 
   it("Should initialize the program", async () => {
     // Airdrop SOL
@@ -54,12 +410,13 @@ describe("fortunex", () => {
 
     // Initialize program
     const tx = await program.methods
-      .initialize(platformWallet.publicKey, usdcMint, 30, 50)
+      .initialize(platformWallet.publicKey, usdcMint, 30, 50, new anchor.BN(0))
       .accounts({
         globalState: globalStatePda,
         usdcMint: usdcMint,
         authority: authority.publicKey,
         platformWallet: platformWallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
@@ -87,6 +444,12 @@ describe("fortunex", () => {
         newUsdcMint: null,
         newPlatformFeeBps: newPlatformFeeBps,
         newBonusPoolFeeBps: null,
+        newVrfProgram: null,
+        newVrfAccount: null,
+        newCrankBounty: null,
+        newMaxCreatorCommissionBps: null,
+        newUpdateDelay: null,
+        newGuardian: null,
       })
       .accounts({
         globalState: globalStatePda,
@@ -96,9 +459,20 @@ describe("fortunex", () => {
       .signers([authority])
       .rpc();
 
+    // Fee changes are queued until execute_pending_update applies them
+    let globalStateAccount = await program.account.globalState.fetch(globalStatePda);
+    assert.equal(globalStateAccount.platformFeeBps, 30);
+    assert.equal(globalStateAccount.pendingUpdate.platformFeeBps, newPlatformFeeBps);
+
+    await program.methods
+      .executePendingUpdate()
+      .accounts({ globalState: globalStatePda })
+      .rpc();
+
     // Fetch GlobalState to verify updates
-    const globalStateAccount = await program.account.globalState.fetch(globalStatePda);
+    globalStateAccount = await program.account.globalState.fetch(globalStatePda);
     assert.equal(globalStateAccount.platformFeeBps, newPlatformFeeBps);
+    assert.isNull(globalStateAccount.pendingUpdate);
   });
 
   it("should fail due to invalid platform fee bps", async () => {
@@ -115,6 +489,12 @@ describe("fortunex", () => {
           newUsdcMint: null,
           newPlatformFeeBps: 1200,
           newBonusPoolFeeBps: null,
+          newVrfProgram: null,
+          newVrfAccount: null,
+          newCrankBounty: null,
+          newMaxCreatorCommissionBps: null,
+          newUpdateDelay: null,
+          newGuardian: null,
         })
         .accounts({
           globalState: globalStatePda,
@@ -188,16 +568,25 @@ describe("fortunex", () => {
       creator.publicKey
     );
 
+    // token account receiving the draw bounties of the authority
+    crankTokenAccount = await createAccount(
+      provider.connection,
+      authority,
+      usdcMint,
+      authority.publicKey,
+      Keypair.generate()
+    );
+
     // Create lottery pool
     const drawInterval = 30; // 24 hours
     const tx = await program.methods
-      .initializePool(new anchor.BN(10_000_000), new anchor.BN(4), new anchor.BN(5), new anchor.BN(drawInterval))
+      .initializePool(new anchor.BN(10_000_000), new anchor.BN(4), new anchor.BN(5), new anchor.BN(drawInterval), { equal: { winners: 1 } }, false, { rollover: { maxRollovers: 3 } }, 0)
       .accounts({
         globalState: globalStatePda,
         lotteryPool: lotteryPoolPda,
         poolTokenAccount: poolTokenAccount,
         vaultAuthority: vaultAuthority,
-        usdcMint: usdcMint,
+        mint: usdcMint,
         creatorTokenAccount: creatorTokenAccount,
         authority: creator.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -208,6 +597,9 @@ describe("fortunex", () => {
 
     console.log("Initialize pool transaction signature:", tx);
 
+    // A single ticket page holds every ticket of the pool
+    await addTicketPage(poolsCount, creator);
+
     // Verify pool creation
     const pool = await program.account.lotteryPool.fetch(lotteryPoolPda);
     console.log("Lottery pool created:", pool);
@@ -307,6 +699,7 @@ describe("fortunex", () => {
           Buffer.from(USER_TICKET_SEED),
           user.publicKey.toBuffer(),
           new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+          new anchor.BN(0).toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -318,14 +711,16 @@ describe("fortunex", () => {
       }
 
       let tx = await program.methods
-        .buyTicket(new anchor.BN(poolId), new anchor.BN(quantity))
+        .buyTicket(new anchor.BN(poolId), new anchor.BN(quantity), null)
         .accounts({
           globalState: globalStatePda,
           lotteryPool: lotteryPoolPda,
           userTicket: userTicketPda,
+          ticketPage: await currentTicketPage(poolId),
           userTokenAccount: userTokenAccount,
           poolTokenAccount: poolTokenAccount,
           user: user.publicKey,
+          mint: usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -367,12 +762,13 @@ describe("fortunex", () => {
         Buffer.from(USER_TICKET_SEED),
         participant.publicKey.toBuffer(),
         new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
 
     let participantTicket = await program.account.userTicket.fetch(participantTicketPda);
-    let ticketNumber = participantTicket.tickets[0].ticketNumber
+    let ticketNumber = participantTicket.tickets[0].startTicket
 
     let cancelTx = await program.methods
       .cancelTicket(new anchor.BN(poolId), ticketNumber)
@@ -385,6 +781,7 @@ describe("fortunex", () => {
         vaultAuthority: vaultAuthority,
         platformTokenAccount: platformTokenAccount,
         user: participant.publicKey,
+        mint: usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
@@ -410,25 +807,9 @@ describe("fortunex", () => {
     console.log(`Pool Token Account Balance: ${balance.value.uiAmount} USDC`);
 
     // === 🎯 Step 4: Draw winner ===
-    const [drawHistoryPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("draw_history"),
-        new anchor.BN(poolId).toArrayLike(Buffer, "le", 8),
-      ],
-      program.programId
-    );
+    const drawHistoryPda = await nextDrawHistory(poolId);
 
     const poolBefore = await program.account.lotteryPool.fetch(lotteryPoolPda);
-    const remainingAccounts = await Promise.all(
-      poolBefore.ticketsSold.map(async (participant: PublicKey) => {
-        const ata = await getAssociatedTokenAddress(usdcMint, participant);
-        return {
-          pubkey: ata,
-          isSigner: false,
-          isWritable: true,
-        };
-      })
-    );
 
     function sleep() {
       console.log(`Waiting for draw time....`);
@@ -440,6 +821,7 @@ describe("fortunex", () => {
     const [bonusTokenAccount] = PublicKey.findProgramAddressSync(
       [
         Buffer.from(BONUS_AUTHORITY_SEED),
+        usdcMint.toBuffer(),
       ],
       program.programId
     );
@@ -459,6 +841,8 @@ describe("fortunex", () => {
     );
     console.log(`Bonus Pool Token Account Balance before draw: ${balance.value.uiAmount} USDC`);
 
+    await commitDraw(poolId);
+
     const drawTx = await program.methods
       .drawWinner(new anchor.BN(poolId))
       .accounts({
@@ -469,13 +853,15 @@ describe("fortunex", () => {
         vaultAuthority: vaultAuthority,
         platformTokenAccount: platformTokenAccount,
         creatorTokenAccount: creatorTokenAccount,
+        crankTokenAccount: crankTokenAccount,
         crank: authority.publicKey,
+        mint: usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
-      .remainingAccounts(remainingAccounts)
       .signers([authority])
       .rpc();
+    const resolveTx = await resolveWinners(poolId);
 
     console.log("\n🎉 Draw transaction signature:", drawTx);
 
@@ -489,9 +875,16 @@ describe("fortunex", () => {
     }
 
     const drawHistory = await program.account.drawHistory.fetch(drawHistoryPda);
+
+    // The owners of the winning tickets are published once resolved
+    const [resolved] = await eventsOf(resolveTx);
+    assert.equal(resolved.name, "winnersResolved");
+    assert.equal(resolved.data.poolId.toNumber(), poolId);
+    assert.ok(resolved.data.winners[0].winner.equals(drawHistory.winners[0].winner));
+
     console.log("\n🏆 Draw History:", {
-      winner: drawHistory.winner.toBase58(),
-      winningTicket: drawHistory.winningTicket.toString(),
+      winner: drawHistory.winners[0].winner.toBase58(),
+      winningTicket: drawHistory.winners[0].ticket.toString(),
       prizeAmount: drawHistory.prizeAmount.toString(),
     });
 
@@ -509,5 +902,2123 @@ describe("fortunex", () => {
       bonusTokenAccount
     );
     console.log(`Bonus Pool Token Account Balance after draw: ${balance.value.uiAmount} USDC`);
+
+    // === 🏆 Step 6: Claim the prize on behalf of the winner ===
+    const winnerAta = await getAssociatedTokenAddress(usdcMint, drawHistory.winners[0].winner);
+    const winnerBefore = await provider.connection.getTokenAccountBalance(winnerAta);
+
+    const { signature: claimTx } = await claimPrize(poolId, crank);
+
+    const winnerAfter = await provider.connection.getTokenAccountBalance(winnerAta);
+    assert.equal(
+      Number(winnerAfter.value.amount) - Number(winnerBefore.value.amount),
+      drawHistory.prizeAmount.toNumber()
+    );
+
+    const [claimed] = await eventsOf(claimTx);
+    assert.equal(claimed.name, "prizeClaimed");
+    assert.equal(claimed.data.place, 0);
+    assert.equal(claimed.data.amount.toNumber(), drawHistory.prizeAmount.toNumber());
+    console.log(`Winner Token Account Balance after claim: ${winnerAfter.value.uiAmount} USDC`);
+
+    try {
+      await claimPrize(poolId, crank);
+      assert.fail("Expected the second claim to fail");
+    } catch (err) {
+      assert.include(err.toString(), "PrizeAlreadyClaimed");
+    }
+  });
+
+  it("Should settle a draw with a VRF result from the mock oracle", async () => {
+    const crank = authority;
+    const vrfAccount = Keypair.generate();
+
+    // === Step 1: create a new pool and sell it out ===
+    const poolId = await createPool(2, 2, 10);
+    const pool = poolAccounts(poolId);
+
+    for (let i = 0; i < 2; i++) {
+      await buyTickets(await createParticipant(10_000_000), poolId, 1);
+    }
+
+    // === Step 2: point the program at the mock oracle ===
+    await vrfProgram.methods
+      .initialize()
+      .accounts({
+        vrf: vrfAccount.publicKey,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority, vrfAccount])
+      .rpc();
+
+    await program.methods
+      .updateGlobalState({
+        newPlatformWallet: null,
+        newUsdcMint: null,
+        newPlatformFeeBps: null,
+        newBonusPoolFeeBps: null,
+        newVrfProgram: vrfProgram.programId,
+        newVrfAccount: vrfAccount.publicKey,
+        newCrankBounty: null,
+        newMaxCreatorCommissionBps: null,
+        newUpdateDelay: null,
+        newGuardian: null,
+      })
+      .accounts({
+        globalState: globalStatePda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId
+      })
+      .signers([authority])
+      .rpc();
+
+    // === Step 3: request the draw once draw time has passed ===
+    let poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    waitUntil(poolState.drawTime.toNumber());
+
+    // A result account the caller controls is rejected, only the pinned one is accepted
+    const rogueVrf = Keypair.generate();
+    await vrfProgram.methods
+      .initialize()
+      .accounts({
+        vrf: rogueVrf.publicKey,
+        authority: crank.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([crank, rogueVrf])
+      .rpc();
+
+    try {
+      await program.methods
+        .requestDraw(new anchor.BN(poolId))
+        .accounts({
+          globalState: globalStatePda,
+          lotteryPool: pool.lotteryPool,
+          vrf: rogueVrf.publicKey,
+          crank: crank.publicKey,
+        })
+        .signers([crank])
+        .rpc();
+
+      assert.fail("Expected request to fail with an unpinned VRF account");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidVrfAccount");
+    }
+
+    // Anyone can request the draw once draw time has passed, not only whitelisted creators
+    const stranger = await createParticipant(0);
+    await program.methods
+      .requestDraw(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        vrf: vrfAccount.publicKey,
+        crank: stranger.user.publicKey,
+      })
+      .signers([stranger.user])
+      .rpc();
+
+    poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.deepEqual(poolState.status, { drawing: {} });
+    assert.ok(poolState.vrfAccount.equals(vrfAccount.publicKey));
+
+    const platformTokenAccount = await createAccount(
+      provider.connection,
+      authority,
+      usdcMint,
+      platformWallet.publicKey,
+      Keypair.generate()
+    );
+
+
+    const drawAccounts = {
+      globalState: globalStatePda,
+      lotteryPool: pool.lotteryPool,
+      drawHistory: await nextDrawHistory(poolId),
+      poolTokenAccount: pool.poolTokenAccount,
+      vaultAuthority: pool.vaultAuthority,
+      platformTokenAccount: platformTokenAccount,
+      bonusPoolTokenAccount: bonusTokenAccountFor(usdcMint),
+      creatorTokenAccount: creatorTokenAccount,
+      crankTokenAccount: crankTokenAccount,
+      crank: crank.publicKey,
+      slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+      mint: usdcMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+
+    // === Step 4: settling before the oracle answers must fail ===
+    try {
+      await program.methods
+        .settleDraw(new anchor.BN(poolId))
+        .accounts({ draw: drawAccounts, vrf: vrfAccount.publicKey })
+        .signers([crank])
+        .rpc();
+
+      assert.fail("Expected settle to fail before the VRF round is fulfilled");
+    } catch (err) {
+      assert.include(err.toString(), "VrfRoundNotFulfilled");
+    }
+
+    const fulfill = () =>
+      vrfProgram.methods
+        .fulfill(Array.from(Keypair.generate().publicKey.toBytes()))
+        .accounts({
+          vrf: vrfAccount.publicKey,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+    // === Step 5: a round published after the next one cannot settle the draw ===
+    await fulfill();
+    await fulfill();
+
+    try {
+      await program.methods
+        .settleDraw(new anchor.BN(poolId))
+        .accounts({ draw: drawAccounts, vrf: vrfAccount.publicKey })
+        .signers([crank])
+        .rpc();
+
+      assert.fail("Expected settle to fail once the requested round was missed");
+    } catch (err) {
+      assert.include(err.toString(), "VrfRoundMissed");
+    }
+
+    // The missed request can be replaced right away
+    await program.methods
+      .requestDraw(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        vrf: vrfAccount.publicKey,
+        crank: crank.publicKey,
+      })
+      .signers([crank])
+      .rpc();
+
+    poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.equal(poolState.vrfRequestRound.toNumber(), 2);
+
+    // === Step 6: oracle publishes the next round, settle picks the winner ===
+    await fulfill();
+
+    const settleTx = await program.methods
+      .settleDraw(new anchor.BN(poolId))
+      .accounts({ draw: drawAccounts, vrf: vrfAccount.publicKey })
+      .signers([crank])
+      .rpc();
+    await resolveWinners(poolId);
+
+    console.log("\n🎉 Settle draw transaction signature:", settleTx);
+
+    poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.deepEqual(poolState.status, { completed: {} });
+
+    const drawHistory = await program.account.drawHistory.fetch(await latestDrawHistory(poolId));
+    assert.ok(drawHistory.winners[0].winner.equals(poolState.winners[0]));
+    assert.ok(drawHistory.randomness.vrf.account.equals(vrfAccount.publicKey));
+    console.log("\n🏆 VRF Draw History:", {
+      winner: drawHistory.winners[0].winner.toBase58(),
+      winningTicket: drawHistory.winners[0].ticket.toString(),
+      prizeAmount: drawHistory.prizeAmount.toString(),
+    });
+  });
+
+  it("Should mix revealed entropy into the draw seed", async () => {
+    const poolId = await createPool(2, 2, 30);
+    const pool = poolAccounts(poolId);
+
+    // === Step 1: both participants commit to hash(user || value) ===
+    const participants = [];
+    const values: Buffer[] = [];
+    for (let i = 0; i < 2; i++) {
+      const participant = await createParticipant(10_000_000);
+      const value = anchor.web3.Keypair.generate().publicKey.toBuffer();
+      const commitment = createHash("sha256")
+        .update(Buffer.concat([participant.user.publicKey.toBuffer(), value]))
+        .digest();
+
+      await buyTickets(participant, poolId, 1, Array.from(commitment));
+      participants.push(participant);
+      values.push(value);
+    }
+
+    // === Step 2: only the first participant reveals inside the window ===
+    let poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    const revealWindowStart =
+      poolState.drawTime.toNumber() - poolState.drawInterval.toNumber() / 2;
+    waitUntil(revealWindowStart);
+
+    await program.methods
+      .revealEntropy(new anchor.BN(poolId), Array.from(values[0]))
+      .accounts({
+        lotteryPool: pool.lotteryPool,
+        userTicket: userTicketAccount(participants[0].user.publicKey, poolId),
+        user: participants[0].user.publicKey,
+      })
+      .signers([participants[0].user])
+      .rpc();
+
+    // Revealing a value that was never committed must fail
+    try {
+      await program.methods
+        .revealEntropy(new anchor.BN(poolId), Array.from(values[0]))
+        .accounts({
+          lotteryPool: pool.lotteryPool,
+          userTicket: userTicketAccount(participants[1].user.publicKey, poolId),
+          user: participants[1].user.publicKey,
+        })
+        .signers([participants[1].user])
+        .rpc();
+
+      assert.fail("Expected reveal to fail for a wrong value");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidReveal");
+    }
+
+    // === Step 3: draw and check the transcript reproduces the seed ===
+    poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    waitUntil(poolState.drawTime.toNumber());
+    await commitDraw(poolId);
+
+    const platformTokenAccount = await getAssociatedTokenAddress(
+      usdcMint,
+      platformWallet.publicKey
+    );
+
+    await program.methods
+      .drawWinner(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        drawHistory: await nextDrawHistory(poolId),
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        platformTokenAccount: platformTokenAccount,
+        creatorTokenAccount: creatorTokenAccount,
+        crankTokenAccount: crankTokenAccount,
+        crank: authority.publicKey,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        mint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await resolveWinners(poolId);
+
+    const drawHistory = await program.account.drawHistory.fetch(await latestDrawHistory(poolId));
+    assert.equal(drawHistory.reveals.length, 1);
+    assert.ok(drawHistory.reveals[0].user.equals(participants[0].user.publicKey));
+
+    const seed = createHash("sha256")
+      .update(Buffer.from(drawHistory.reveals[0].value))
+      .update(Buffer.from(drawHistory.slotHash))
+      .update(new anchor.BN(poolId).toArrayLike(Buffer, "le", 8))
+      .digest();
+    assert.deepEqual(Array.from(seed), drawHistory.randomSeed);
+  });
+
+  it("Should split the prize between tiered places", async () => {
+    // A schedule that does not add up to 100% is rejected
+    try {
+      await createPool(3, 3, 10, { tiered: { sharesBps: [7000, 2000] } });
+      assert.fail("Expected an invalid prize schedule to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidPrizeSchedule");
+    }
+
+    const poolId = await createPool(3, 3, 10, {
+      tiered: { sharesBps: [7000, 2000, 1000] },
+    });
+    const pool = poolAccounts(poolId);
+
+    // === Step 1: three participants sell out the pool ===
+    for (let i = 0; i < 3; i++) {
+      await buyTickets(await createParticipant(10_000_000), poolId, 1);
+    }
+
+    // === Step 2: draw all three places ===
+    const poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    waitUntil(poolState.drawTime.toNumber());
+    await commitDraw(poolId);
+
+    const platformTokenAccount = await getAssociatedTokenAddress(
+      usdcMint,
+      platformWallet.publicKey
+    );
+
+    await program.methods
+      .drawWinner(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        drawHistory: await nextDrawHistory(poolId),
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        platformTokenAccount: platformTokenAccount,
+        creatorTokenAccount: creatorTokenAccount,
+        crankTokenAccount: crankTokenAccount,
+        crank: authority.publicKey,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        mint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await resolveWinners(poolId);
+
+    const drawHistory = await program.account.drawHistory.fetch(await latestDrawHistory(poolId));
+    assert.equal(drawHistory.winners.length, 3);
+
+    // Every place is won by a distinct ticket and the places add up to the prize
+    const tickets = drawHistory.winners.map((w) => w.ticket.toNumber());
+    assert.equal(new Set(tickets).size, 3);
+    const total = drawHistory.winners.reduce((sum, w) => sum + w.amount.toNumber(), 0);
+    assert.equal(total, drawHistory.prizeAmount.toNumber());
+
+    // The fee lines and the prize add up to the prize pool they were taken from
+    assert.equal(drawHistory.version, 1);
+    assert.equal(
+      drawHistory.totalPrize.toNumber(),
+      drawHistory.prizeAmount.toNumber() +
+        drawHistory.platformFee.toNumber() +
+        drawHistory.bonusPoolFee.toNumber() +
+        drawHistory.commission.toNumber() +
+        drawHistory.crankBounty.toNumber()
+    );
+    assert.equal(drawHistory.platformFeeBps, poolState.platformFeeBps);
+    assert.equal(drawHistory.ticketPrice.toNumber(), poolState.ticketPrice.toNumber());
+    assert.equal(drawHistory.participants.toNumber(), 3);
+    assert.ok(drawHistory.randomness.slotHashes);
+
+    // Only pools with a draw history of the first release, one record per pool, can be migrated
+    const [legacyDrawHistory] = PublicKey.findProgramAddressSync(
+      [Buffer.from("draw_history"), new anchor.BN(poolId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    try {
+      await program.methods
+        .migrateDrawHistory(new anchor.BN(poolId))
+        .accounts({
+          globalState: globalStatePda,
+          legacyDrawHistory,
+          drawHistory: await nextDrawHistory(poolId),
+          payer: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+      assert.fail("Expected the migration to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "ConstraintOwner");
+    }
+
+    assert.isAtLeast(
+      drawHistory.winners[0].amount.toNumber(),
+      drawHistory.winners[1].amount.toNumber()
+    );
+
+    // === Step 3: each place is claimed on its own ===
+    for (let place = 0; place < 3; place++) {
+      await claimPrize(poolId, authority, place);
+    }
+
+    const claimed = await program.account.drawHistory.fetch(await latestDrawHistory(poolId));
+    assert.ok(claimed.winners.every((w) => w.claimed));
+  });
+
+  it("Should reopen a recurring pool for the next round after a draw", async () => {
+    const poolId = await createPool(1, 1, 10, { equal: { winners: 1 } }, true);
+    const participant = await createParticipant(20_000_000);
+
+    // === Step 1: sell out and draw round 0 ===
+    await buyTickets(participant, poolId, 1);
+
+    const round0 = poolAccounts(poolId);
+    const round0History = await nextDrawHistory(poolId);
+    let poolState = await program.account.lotteryPool.fetch(round0.lotteryPool);
+    const firstDrawTime = poolState.drawTime.toNumber();
+    waitUntil(firstDrawTime);
+    await commitDraw(poolId);
+
+    const platformTokenAccount = await getAssociatedTokenAddress(
+      usdcMint,
+      platformWallet.publicKey
+    );
+
+    await program.methods
+      .drawWinner(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: round0.lotteryPool,
+        drawHistory: round0History,
+        poolTokenAccount: round0.poolTokenAccount,
+        vaultAuthority: round0.vaultAuthority,
+        platformTokenAccount: platformTokenAccount,
+        creatorTokenAccount: creatorTokenAccount,
+        crankTokenAccount: crankTokenAccount,
+        crank: authority.publicKey,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        mint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await resolveWinners(poolId);
+
+    // === Step 2: the pool is reset for round 1 ===
+    poolState = await program.account.lotteryPool.fetch(round0.lotteryPool);
+    assert.equal(poolState.round.toNumber(), 1);
+    assert.deepEqual(poolState.status, { active: {} });
+    assert.equal(poolState.ticketsSold.toNumber(), 0);
+    assert.equal(poolState.prizePool.toNumber(), 0);
+    assert.equal(poolState.drawCommitSlot.toNumber(), 0);
+    assert.isAbove(poolState.drawTime.toNumber(), firstDrawTime);
+    assert.equal(
+      (poolState.drawTime.toNumber() - firstDrawTime) % poolState.drawInterval.toNumber(),
+      0
+    );
+
+    const history = await program.account.drawHistory.fetch(round0History);
+    assert.equal(history.round.toNumber(), 0);
+    assert.ok(history.payer.equals(authority.publicKey));
+    assert.equal(history.drawIndex.toNumber(), poolState.drawIndex.toNumber());
+
+    // The draw is listed in the recent draws without fetching its history
+    const [recentDrawsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("recent_draws")],
+      program.programId
+    );
+    const recentDraws = await program.account.recentDraws.fetch(recentDrawsPda);
+    const summary = recentDraws.draws.find((d) => d.drawIndex.eq(history.drawIndex));
+    assert.equal(summary.poolId.toNumber(), poolId);
+    assert.equal(summary.prizeAmount.toNumber(), history.prizeAmount.toNumber());
+    assert.ok(history.winners[0].winner.equals(participant.user.publicKey));
+
+    // === Step 3: the same participant buys into round 1 with a fresh ticket account ===
+    await buyTickets(participant, poolId, 1);
+    const round1Ticket = await program.account.userTicket.fetch(
+      userTicketAccount(participant.user.publicKey, poolId, 1)
+    );
+    assert.equal(round1Ticket.round.toNumber(), 1);
+    assert.equal(round1Ticket.tickets.length, 1);
+
+    // The round 0 record cannot be closed while its prize is unclaimed
+    const closeDrawHistory = () =>
+      program.methods
+        .closeDrawHistory(new anchor.BN(poolId), history.drawIndex)
+        .accounts({
+          globalState: globalStatePda,
+          lotteryPool: round0.lotteryPool,
+          drawHistory: round0History,
+          payer: history.payer,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    try {
+      await closeDrawHistory();
+      assert.fail("Expected closing a draw history with an unclaimed prize to fail");
+    } catch (err) {
+      assert.include(err.toString(), "PrizesNotClaimed");
+    }
+
+    // The round 0 prize can still be claimed while round 1 is open
+    await claimPrize(poolId, authority, 0, history.drawIndex.toNumber());
+    const claimed = await program.account.drawHistory.fetch(round0History);
+    assert.ok(claimed.winners[0].claimed);
+
+    // === Step 4: the settled round 0 record is closed, the recent draws stay while pools are active ===
+    await closeDrawHistory();
+    assert.isNull(await program.account.drawHistory.fetchNullable(round0History));
+
+    try {
+      await program.methods
+        .closeRecentDraws()
+        .accounts({
+          globalState: globalStatePda,
+          recentDraws: recentDrawsPda,
+          payer: recentDraws.payer,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+      assert.fail("Expected closing the recent draws with active pools to fail");
+    } catch (err) {
+      assert.include(err.toString(), "PoolsStillActive");
+    }
+  });
+
+  it("Should refund every ticket once the rollovers run out", async () => {
+    const poolId = await createPool(3, 3, 5, { equal: { winners: 1 } }, false, {
+      rollover: { maxRollovers: 1 },
+    });
+    const pool = poolAccounts(poolId);
+
+    // === Step 1: only two of the three required tickets are sold ===
+    const participants = [];
+    for (let i = 0; i < 2; i++) {
+      const participant = await createParticipant(10_000_000);
+      await buyTickets(participant, poolId, 1);
+      participants.push(participant);
+    }
+
+    // === Step 2: the first missed draw rolls over, the second one fails the pool ===
+    let poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    waitUntil(poolState.drawTime.toNumber());
+    await commitDraw(poolId);
+
+    poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.equal(poolState.rollovers, 1);
+    assert.deepEqual(poolState.status, { active: {} });
+
+    waitUntil(poolState.drawTime.toNumber());
+    await commitDraw(poolId);
+
+    poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.deepEqual(poolState.status, { refunding: {} });
+
+    // New tickets cannot be bought once the pool is refunding
+    try {
+      await buyTickets(await createParticipant(10_000_000), poolId, 1);
+      assert.fail("Expected buying into a refunding pool to fail");
+    } catch (err) {
+      assert.include(err.toString(), "PoolNotActive");
+    }
+
+    // === Step 3: the first participant pulls their refund ===
+    const first = participants[0];
+    const firstTicket = await program.account.userTicket.fetch(
+      userTicketAccount(first.user.publicKey, poolId)
+    );
+    await program.methods
+      .refundTicket(new anchor.BN(poolId), firstTicket.tickets[0].startTicket)
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        userTicket: userTicketAccount(first.user.publicKey, poolId),
+        userTokenAccount: first.tokenAccount,
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        user: first.user.publicKey,
+        mint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([first.user])
+      .rpc();
+
+    // === Step 4: anyone pushes the remaining refunds ===
+    const second = participants[1];
+    await program.methods
+      .refundAll(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        payer: authority.publicKey,
+        mint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        {
+          pubkey: userTicketAccount(second.user.publicKey, poolId),
+          isWritable: true,
+          isSigner: false,
+        },
+        { pubkey: second.tokenAccount, isWritable: true, isSigner: false },
+      ])
+      .signers([authority])
+      .rpc();
+
+    // Both participants got their full ticket price back and the vault is empty
+    for (const participant of participants) {
+      const balance = await provider.connection.getTokenAccountBalance(
+        participant.tokenAccount
+      );
+      assert.equal(Number(balance.value.amount), 10_000_000);
+    }
+
+    poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.equal(poolState.ticketsSold.toNumber(), 0);
+    assert.equal(poolState.prizePool.toNumber(), 0);
+    const vault = await provider.connection.getTokenAccountBalance(pool.poolTokenAccount);
+    assert.equal(Number(vault.value.amount), 0);
+
+    // === Step 5: the refunded ticket accounts and the empty pool are closed for their rent ===
+    for (const participant of participants) {
+      const userTicket = userTicketAccount(participant.user.publicKey, poolId);
+      await program.methods
+        .closeUserTicket(new anchor.BN(poolId), new anchor.BN(0))
+        .accounts({
+          lotteryPool: pool.lotteryPool,
+          userTicket,
+          user: participant.user.publicKey,
+        })
+        .signers([participant.user])
+        .rpc();
+      assert.isNull(await program.account.userTicket.fetchNullable(userTicket));
+    }
+
+    // The ticket pages have to be closed with the pool
+    try {
+      await closePool(poolId, false);
+      assert.fail("Expected closing the pool without its ticket pages to fail");
+    } catch (err) {
+      assert.include(err.toString(), "TicketPagesNotClosed");
+    }
+
+    const pages = await closePool(poolId);
+    assert.isNull(await provider.connection.getAccountInfo(pool.lotteryPool));
+    assert.isNull(await provider.connection.getAccountInfo(pool.poolTokenAccount));
+    for (const page of pages) {
+      assert.isNull(await provider.connection.getAccountInfo(page));
+    }
+  });
+
+  it("Should stop a recurring pool and close it once its last round is settled", async () => {
+    const poolId = await createPool(1, 5, 5, { equal: { winners: 1 } }, true, { refund: {} });
+    const pool = poolAccounts(poolId);
+
+    // Recurring pools never finish on their own
+    try {
+      await closePool(poolId);
+      assert.fail("Expected closing a recurring pool to fail");
+    } catch (err) {
+      assert.include(err.toString(), "PoolNotSettled");
+    }
+
+    // === Step 1: the creator stops the pool after the current round ===
+    const stopPool = () =>
+      program.methods
+        .stopPool(new anchor.BN(poolId))
+        .accounts({ lotteryPool: pool.lotteryPool, creator: authority.publicKey })
+        .signers([authority])
+        .rpc();
+    await stopPool();
+    try {
+      await stopPool();
+      assert.fail("Expected stopping a stopped pool to fail");
+    } catch (err) {
+      assert.include(err.toString(), "PoolNotRecurring");
+    }
+
+    // === Step 2: the round ends without tickets, so the pool finishes instead of reopening ===
+    let poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    waitUntil(poolState.drawTime.toNumber());
+    await commitDraw(poolId);
+
+    poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.equal(poolState.round.toNumber(), 0);
+    assert.deepEqual(poolState.status, { refunding: {} });
+
+    // === Step 3: the pool and its ticket pages are closed ===
+    await closePool(poolId);
+    assert.isNull(await provider.connection.getAccountInfo(pool.lotteryPool));
+  });
+
+  it("Should record purchases as ticket ranges and reissue cancelled tickets", async () => {
+    const poolId = await createPool(2, 5, 30);
+    const pool = poolAccounts(poolId);
+
+    // Ranges for every ticket of a 5 ticket pool fit in one page, a second page is rejected
+    try {
+      await addTicketPage(poolId, authority);
+      assert.fail("Expected a second ticket page to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "TooManyTicketPages");
+    }
+
+    // Each purchase is recorded as a single range
+    const first = await createParticipant(30_000_000);
+    const second = await createParticipant(10_000_000);
+    await buyTickets(first, poolId, 3);
+    await buyTickets(second, poolId, 1);
+
+    const page = await program.account.ticketPage.fetch(ticketPage(poolId, 0));
+    assert.ok(page.pool.equals(pool.lotteryPool));
+    assert.equal(page.ranges[0].startTicket.toNumber(), 0);
+    assert.equal(page.ranges[0].count.toNumber(), 3);
+    assert.ok(page.ranges[0].owner.equals(first.user.publicKey));
+    assert.equal(page.ranges[1].startTicket.toNumber(), 3);
+    assert.equal(page.ranges[1].count.toNumber(), 1);
+    assert.ok(page.ranges[1].owner.equals(second.user.publicKey));
+
+    const firstTicketPda = userTicketAccount(first.user.publicKey, poolId);
+    let firstTicket = await program.account.userTicket.fetch(firstTicketPda);
+    assert.equal(firstTicket.tickets.length, 1);
+    assert.equal(firstTicket.tickets[0].count.toNumber(), 3);
+
+    // Cancelling a ticket inside a range splits the user's range, ticket numbers stay put
+    const platformTokenAccount = await getAssociatedTokenAddress(
+      usdcMint,
+      platformWallet.publicKey
+    );
+    await program.methods
+      .cancelTicket(new anchor.BN(poolId), new anchor.BN(1))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        userTicket: firstTicketPda,
+        userTokenAccount: first.tokenAccount,
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        platformTokenAccount: platformTokenAccount,
+        user: first.user.publicKey,
+        mint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([first.user])
+      .rpc();
+
+    firstTicket = await program.account.userTicket.fetch(firstTicketPda);
+    assert.deepEqual(
+      firstTicket.tickets.map((t) => [t.startTicket.toNumber(), t.count.toNumber()]),
+      [
+        [0, 1],
+        [2, 1],
+      ]
+    );
+
+    let poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.equal(poolState.ticketsSold.toNumber(), 3);
+    assert.equal(poolState.nextTicket.toNumber(), 4);
+    assert.equal(poolState.rangeCount.toNumber(), 2);
+    assert.deepEqual(
+      poolState.cancelledTickets.map((t) => t.toNumber()),
+      [1]
+    );
+
+    // The next buyer is reissued the vacated ticket number before a new range is issued
+    const third = await createParticipant(20_000_000);
+    await buyTickets(third, poolId, 2);
+
+    const thirdTicket = await program.account.userTicket.fetch(
+      userTicketAccount(third.user.publicKey, poolId)
+    );
+    assert.deepEqual(
+      thirdTicket.tickets.map((t) => [t.startTicket.toNumber(), t.count.toNumber()]),
+      [
+        [1, 1],
+        [4, 1],
+      ]
+    );
+
+    poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.equal(poolState.ticketsSold.toNumber(), 5);
+    assert.equal(poolState.nextTicket.toNumber(), 5);
+    assert.equal(poolState.rangeCount.toNumber(), 4);
+    assert.equal(poolState.cancelledTickets.length, 0);
+
+    // The reissued number gets a range of its own, overriding the range it was first bought in
+    const reissuedPage = await program.account.ticketPage.fetch(ticketPage(poolId, 0));
+    assert.equal(reissuedPage.ranges[2].startTicket.toNumber(), 1);
+    assert.equal(reissuedPage.ranges[2].count.toNumber(), 1);
+    assert.ok(reissuedPage.ranges[2].owner.equals(third.user.publicKey));
+    assert.equal(reissuedPage.ranges[3].startTicket.toNumber(), 4);
+    assert.ok(reissuedPage.ranges[3].owner.equals(third.user.publicKey));
+  });
+
+  it("Should cap ticket ranges at purchase and keep room for cancels", async () => {
+    const poolId = await createPool(1, 100, 3600);
+    const pool = poolAccounts(poolId);
+
+    // One range of three tickets followed by single tickets between someone else's,
+    // so none of them can be merged
+    const holder = await createParticipant(300_000_000);
+    const other = await createParticipant(300_000_000);
+    await buyTickets(holder, poolId, 3);
+    for (let i = 0; i < 19; i++) {
+      await buyTickets(other, poolId, 1);
+      await buyTickets(holder, poolId, 1);
+    }
+
+    const holderTicketPda = userTicketAccount(holder.user.publicKey, poolId);
+    let holderTicket = await program.account.userTicket.fetch(holderTicketPda);
+    assert.equal(holderTicket.tickets.length, 20);
+
+    // A purchase needing a 21st range is rejected
+    await buyTickets(other, poolId, 1);
+    try {
+      await buyTickets(holder, poolId, 1);
+      assert.fail("Expected a purchase beyond the range limit to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "TooManyTicketRanges");
+    }
+
+    // Cancelling inside the first range still splits it
+    await program.methods
+      .cancelTicket(new anchor.BN(poolId), new anchor.BN(1))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        userTicket: holderTicketPda,
+        userTokenAccount: holder.tokenAccount,
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        platformTokenAccount: await getAssociatedTokenAddress(usdcMint, platformWallet.publicKey),
+        user: holder.user.publicKey,
+        mint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([holder.user])
+      .rpc();
+
+    holderTicket = await program.account.userTicket.fetch(holderTicketPda);
+    assert.equal(holderTicket.tickets.length, 21);
+    assert.deepEqual(
+      holderTicket.tickets.slice(0, 2).map((t) => [t.startTicket.toNumber(), t.count.toNumber()]),
+      [
+        [0, 1],
+        [2, 1],
+      ]
+    );
+  });
+
+  it("Should only reissue cancelled tickets the buyer has ranges for", async () => {
+    const poolId = await createPool(1, 100, 3600);
+    const pool = poolAccounts(poolId);
+
+    // Alternating purchases leave both users with 19 single ticket ranges
+    const other = await createParticipant(300_000_000);
+    const holder = await createParticipant(300_000_000);
+    for (let i = 0; i < 19; i++) {
+      await buyTickets(other, poolId, 1);
+      await buyTickets(holder, poolId, 1);
+    }
+
+    // The other user cancels scattered tickets 0, 2 and 4
+    for (const ticketNumber of [0, 2, 4]) {
+      await program.methods
+        .cancelTicket(new anchor.BN(poolId), new anchor.BN(ticketNumber))
+        .accounts({
+          globalState: globalStatePda,
+          lotteryPool: pool.lotteryPool,
+          userTicket: userTicketAccount(other.user.publicKey, poolId),
+          userTokenAccount: other.tokenAccount,
+          poolTokenAccount: pool.poolTokenAccount,
+          vaultAuthority: pool.vaultAuthority,
+          platformTokenAccount: await getAssociatedTokenAddress(usdcMint, platformWallet.publicKey),
+          user: other.user.publicKey,
+          mint: usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([other.user])
+        .rpc();
+    }
+
+    // With a single range left, the holder gets new tickets instead of three separate numbers
+    await buyTickets(holder, poolId, 3);
+
+    const holderTicket = await program.account.userTicket.fetch(
+      userTicketAccount(holder.user.publicKey, poolId)
+    );
+    assert.equal(holderTicket.tickets.length, 20);
+    assert.equal(holderTicket.tickets[19].startTicket.toNumber(), 38);
+    assert.equal(holderTicket.tickets[19].count.toNumber(), 3);
+
+    let poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.deepEqual(
+      poolState.cancelledTickets.map((t) => t.toNumber()),
+      [0, 2, 4]
+    );
+
+    // A buyer with free ranges is reissued the numbers left over
+    const next = await createParticipant(30_000_000);
+    await buyTickets(next, poolId, 3);
+
+    const nextTicket = await program.account.userTicket.fetch(
+      userTicketAccount(next.user.publicKey, poolId)
+    );
+    assert.deepEqual(
+      nextTicket.tickets.map((t) => [t.startTicket.toNumber(), t.count.toNumber()]),
+      [
+        [0, 1],
+        [2, 1],
+        [4, 1],
+      ]
+    );
+
+    poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.equal(poolState.cancelledTickets.length, 0);
+  });
+
+  it("Should resolve winners whose ranges are on a later ticket page", async () => {
+    const poolId = await createPool(1, 2200, 300, { equal: { winners: 10 } });
+    const pool = poolAccounts(poolId);
+
+    // === Step 1: fill the first page with single ticket ranges ===
+    // Every buyer can hold up to 20 ranges, so ten of them fill the page's 200 ranges
+    for (let i = 0; i < RANGES_PER_PAGE / 20; i++) {
+      const participant = await createParticipant(200_000_000);
+      for (let j = 0; j < 20; j++) {
+        await buyTickets(participant, poolId, 1);
+      }
+    }
+    let poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.equal(poolState.rangeCount.toNumber(), RANGES_PER_PAGE);
+
+    // The next range needs the second page
+    const whale = await createParticipant(20_000_000_000);
+    try {
+      await buyTickets(whale, poolId, 2000);
+      assert.fail("Expected the purchase to need a second ticket page");
+    } catch (err) {
+      assert.include(err.toString(), "AccountNotInitialized");
+    }
+    await addTicketPage(poolId, authority);
+    await buyTickets(whale, poolId, 2000);
+
+    const secondPage = await program.account.ticketPage.fetch(ticketPage(poolId, 1));
+    assert.equal(secondPage.pageIndex, 1);
+    assert.equal(secondPage.ranges[0].startTicket.toNumber(), RANGES_PER_PAGE);
+    assert.equal(secondPage.ranges[0].count.toNumber(), 2000);
+    assert.ok(secondPage.ranges[0].owner.equals(whale.user.publicKey));
+
+    // === Step 2: draw ten places, the second page holds all but 200 of 2200 tickets ===
+    poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    waitUntil(poolState.drawTime.toNumber());
+    await commitDraw(poolId);
+
+    await program.methods
+      .drawWinner(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        drawHistory: await nextDrawHistory(poolId),
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        platformTokenAccount: await getAssociatedTokenAddress(usdcMint, platformWallet.publicKey),
+        creatorTokenAccount: creatorTokenAccount,
+        crankTokenAccount: crankTokenAccount,
+        crank: authority.publicKey,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        mint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    // === Step 3: resolving needs both pages and finds the owners on either ===
+    try {
+      await program.methods
+        .resolveWinners(new anchor.BN(poolId))
+        .accounts({
+          globalState: globalStatePda,
+          lotteryPool: pool.lotteryPool,
+          drawHistory: await latestDrawHistory(poolId),
+          payer: authority.publicKey,
+        })
+        .remainingAccounts((await ticketPageMetas(poolId)).slice(0, 1))
+        .signers([authority])
+        .rpc();
+      assert.fail("Expected resolving without the second page to fail");
+    } catch (err) {
+      assert.include(err.toString(), "TicketPageMissing");
+    }
+    await resolveWinners(poolId);
+
+    const drawHistory = await program.account.drawHistory.fetch(await latestDrawHistory(poolId));
+    const onSecondPage = drawHistory.winners.filter(
+      (w) => w.ticket.toNumber() >= RANGES_PER_PAGE
+    );
+    assert.isAbove(onSecondPage.length, 0);
+    for (const w of onSecondPage) {
+      assert.ok(w.winner.equals(whale.user.publicKey));
+    }
+    for (const w of drawHistory.winners) {
+      assert.notOk(w.winner.equals(PublicKey.default));
+    }
+  });
+
+  it("Should let anyone draw a due pool and pay them the bounty", async () => {
+    const setCrankBounty = async (crankBounty) => {
+      await program.methods
+        .updateGlobalState({
+          newPlatformWallet: null,
+          newUsdcMint: null,
+          newPlatformFeeBps: null,
+          newBonusPoolFeeBps: null,
+          newVrfProgram: null,
+          newVrfAccount: null,
+          newCrankBounty: crankBounty,
+          newMaxCreatorCommissionBps: null,
+          newUpdateDelay: null,
+          newGuardian: null,
+        })
+        .accounts({
+          globalState: globalStatePda,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+      await program.methods
+        .executePendingUpdate()
+        .accounts({ globalState: globalStatePda })
+        .rpc();
+    };
+
+    // A bounty above MAX_CRANK_BOUNTY_BPS is rejected
+    try {
+      await setCrankBounty({ bps: { bps: 101, maxAmount: new anchor.BN(0) } });
+      assert.fail("Expected an excessive crank bounty to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidCrankBounty");
+    }
+
+    // 1% of the prize pool, capped at 0.05 USDC
+    await setCrankBounty({ bps: { bps: 100, maxAmount: new anchor.BN(50_000) } });
+
+    const poolId = await createPool(1, 1, 10);
+    const pool = poolAccounts(poolId);
+    await buyTickets(await createParticipant(10_000_000), poolId, 1);
+
+    // A random account that is not whitelisted drives the draw
+    const stranger = await createParticipant(0);
+    const poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    waitUntil(poolState.drawTime.toNumber());
+    await commitDraw(poolId, stranger.user);
+
+    // The pending commitment cannot be replaced while its slot can still be drawn
+    try {
+      await commitDraw(poolId, stranger.user);
+      assert.fail("Expected a second commit to fail");
+    } catch (err) {
+      assert.include(err.toString(), "DrawAlreadyCommitted");
+    }
+
+    const platformTokenAccount = await getAssociatedTokenAddress(
+      usdcMint,
+      platformWallet.publicKey
+    );
+
+    await program.methods
+      .drawWinner(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        drawHistory: await nextDrawHistory(poolId),
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        platformTokenAccount: platformTokenAccount,
+        creatorTokenAccount: creatorTokenAccount,
+        crankTokenAccount: stranger.tokenAccount,
+        crank: stranger.user.publicKey,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        mint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([stranger.user])
+      .rpc();
+
+    const bounty = await provider.connection.getTokenAccountBalance(
+      stranger.tokenAccount
+    );
+    assert.equal(Number(bounty.value.amount), 50_000);
+
+    await setCrankBounty({ fixed: { amount: new anchor.BN(0) } });
+  });
+
+  it("Should draw the bonus pool among the participants of a bonus round", async () => {
+    const bonusRoundAccounts = (roundId: number) => {
+      const roundIdBytes = new anchor.BN(roundId).toArrayLike(Buffer, "le", 8);
+      return {
+        bonusRound: PublicKey.findProgramAddressSync(
+          [Buffer.from("bonus_round"), roundIdBytes],
+          program.programId
+        )[0],
+        bonusHistory: PublicKey.findProgramAddressSync(
+          [Buffer.from("bonus_history"), roundIdBytes],
+          program.programId
+        )[0],
+      };
+    };
+
+    // === Step 1: start a single jackpot bonus round ===
+    let globalState = await program.account.globalState.fetch(globalStatePda);
+    const roundId = globalState.bonusRoundsCount.toNumber();
+    const { bonusRound, bonusHistory } = bonusRoundAccounts(roundId);
+
+    await program.methods
+      .startBonusRound(new anchor.BN(60), { equal: { winners: 1 } })
+      .accounts({
+        globalState: globalStatePda,
+        bonusRound,
+        mint: usdcMint,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    // === Step 2: a participant buys a ticket and is entered by anyone once it is drawing ===
+    const poolId = await createPool(1, 1, 5);
+    const participant = await createParticipant(10_000_000);
+    await buyTickets(participant, poolId, 1);
+
+    const enterBonusRound = () =>
+      program.methods
+        .enterBonusRound(new anchor.BN(roundId))
+        .accounts({
+          bonusRound,
+          userTicket: userTicketAccount(participant.user.publicKey, poolId),
+          lotteryPool: poolAccounts(poolId).lotteryPool,
+          payer: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+    // Tickets that can still be cancelled do not count
+    try {
+      await enterBonusRound();
+      assert.fail("Expected a cancellable ticket to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "NotEligibleForBonus");
+    }
+
+    const poolState = await program.account.lotteryPool.fetch(poolAccounts(poolId).lotteryPool);
+    waitUntil(poolState.drawTime.toNumber());
+    await commitDraw(poolId);
+
+    await enterBonusRound();
+    try {
+      await enterBonusRound();
+      assert.fail("Expected a second entry to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "AlreadyEnteredBonus");
+    }
+
+    // === Step 3: commit once the round ended and draw ===
+    let round = await program.account.bonusRound.fetch(bonusRound);
+    waitUntil(round.endTime.toNumber());
+
+    const commitBonusDraw = () =>
+      program.methods
+        .commitBonusDraw(new anchor.BN(roundId))
+        .accounts({
+          globalState: globalStatePda,
+          bonusRound,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+          crank: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    const setPause = (paused: number) =>
+      program.methods
+        .setPause(paused)
+        .accounts({ globalState: globalStatePda, signer: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+    // Bonus draws stop with the other draws
+    const PAUSE_DRAW = 4;
+    await setPause(PAUSE_DRAW);
+    try {
+      await commitBonusDraw();
+      assert.fail("Expected the bonus draw to be paused");
+    } catch (err) {
+      assert.include(err.toString(), "OperationPaused");
+    }
+    await setPause(0);
+
+    await commitBonusDraw();
+
+    round = await program.account.bonusRound.fetch(bonusRound);
+    while ((await provider.connection.getSlot()) <= round.drawCommitSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+
+    globalState = await program.account.globalState.fetch(globalStatePda);
+    const bonusBalance = await provider.connection.getTokenAccountBalance(bonusTokenAccountFor(usdcMint));
+    const available =
+      Number(bonusBalance.value.amount) -
+      globalState.acceptedMints
+        .find((m) => m.mint.equals(usdcMint))
+        .bonusReserved.toNumber();
+
+    await program.methods
+      .drawBonus(new anchor.BN(roundId))
+      .accounts({
+        globalState: globalStatePda,
+        bonusRound,
+        bonusHistory,
+        bonusPoolTokenAccount: bonusTokenAccountFor(usdcMint),
+        crank: authority.publicKey,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const history = await program.account.bonusDrawHistory.fetch(bonusHistory);
+    assert.equal(history.winners.length, 1);
+    assert.ok(history.winners[0].winner.equals(participant.user.publicKey));
+    assert.equal(history.winners[0].amount.toNumber(), available);
+
+    // === Step 4: the winner claims the jackpot from the bonus pool ===
+    const winnerTokenAccount = await getAssociatedTokenAddress(
+      usdcMint,
+      participant.user.publicKey
+    );
+    await program.methods
+      .claimBonus(new anchor.BN(roundId), 0)
+      .accounts({
+        globalState: globalStatePda,
+        bonusHistory,
+        bonusPoolTokenAccount: bonusTokenAccountFor(usdcMint),
+        winner: participant.user.publicKey,
+        winnerTokenAccount,
+        mint: usdcMint,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const prize = await provider.connection.getTokenAccountBalance(winnerTokenAccount);
+    assert.equal(Number(prize.value.amount), available);
+
+    globalState = await program.account.globalState.fetch(globalStatePda);
+    const accepted = globalState.acceptedMints.find((m) => m.mint.equals(usdcMint));
+    assert.equal(accepted.bonusReserved.toNumber(), 0);
+  });
+
+  it("Should reject draw intervals outside the allowed range", async () => {
+    for (const drawInterval of [0, -30, 7 * 24 * 60 * 60 + 1]) {
+      try {
+        await createPool(1, 1, drawInterval);
+        assert.fail(`Expected a draw interval of ${drawInterval} to be rejected`);
+      } catch (err) {
+        assert.include(err.toString(), "InvalidDrawInterval");
+      }
+    }
+  });
+
+  it("Should cap the creator commission and only let the creator lower it", async () => {
+    const globalState = await program.account.globalState.fetch(globalStatePda);
+    const maxCommission = globalState.maxCreatorCommissionBps;
+
+    // A commission above the global cap is rejected
+    try {
+      await createPool(1, 1, 30, { equal: { winners: 1 } }, false, { refund: {} }, maxCommission + 1);
+      assert.fail("Expected a commission above the cap to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidCommission");
+    }
+
+    const poolId = await createPool(1, 1, 30, { equal: { winners: 1 } }, false, { refund: {} }, maxCommission);
+    const pool = poolAccounts(poolId);
+
+    const updateCommission = (commissionBps: number) =>
+      program.methods
+        .updatePoolCommission(new anchor.BN(poolId), commissionBps)
+        .accounts({
+          lotteryPool: pool.lotteryPool,
+          creator: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+    // The creator can lower the commission but never raise it again
+    const [lowered] = await eventsOf(await updateCommission(maxCommission - 100));
+    assert.equal(lowered.name, "poolCommissionUpdated");
+    assert.equal(lowered.data.commissionBps, maxCommission - 100);
+    try {
+      await updateCommission(maxCommission);
+      assert.fail("Expected raising the commission to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "CommissionIncrease");
+    }
+
+    const poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.equal(poolState.commissionBps, maxCommission - 100);
+  });
+
+  it("Should transfer the program authority in two steps", async () => {
+    const newAuthority = Keypair.generate();
+
+    const propose = (from: Keypair, to: PublicKey) =>
+      program.methods
+        .proposeAuthority(to)
+        .accounts({ globalState: globalStatePda, authority: from.publicKey })
+        .signers([from])
+        .rpc();
+    const accept = (signer: Keypair) =>
+      program.methods
+        .acceptAuthority()
+        .accounts({ globalState: globalStatePda, newAuthority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    const cancel = () =>
+      program.methods
+        .cancelAuthorityTransfer()
+        .accounts({ globalState: globalStatePda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+    // A proposal can be cancelled before it is accepted
+    await propose(authority, newAuthority.publicKey);
+    await cancel();
+    try {
+      await cancel();
+      assert.fail("Expected cancelling without a pending transfer to fail");
+    } catch (err) {
+      assert.include(err.toString(), "NoPendingAuthority");
+    }
+
+    // Only the proposed key can accept
+    await propose(authority, newAuthority.publicKey);
+    try {
+      await accept(Keypair.generate());
+      assert.fail("Expected a key that was not proposed to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidPendingAuthority");
+    }
+
+    await accept(newAuthority);
+    let globalState = await program.account.globalState.fetch(globalStatePda);
+    assert.ok(globalState.authority.equals(newAuthority.publicKey));
+    assert.ok(globalState.pendingAuthority.equals(PublicKey.default));
+
+    // Hand the program back to the original authority for the other tests
+    await propose(newAuthority, authority.publicKey);
+    await accept(authority);
+    globalState = await program.account.globalState.fetch(globalStatePda);
+    assert.ok(globalState.authority.equals(authority.publicKey));
+  });
+
+  it("Should delay fee changes and keep the fees of existing pools", async () => {
+    const queueUpdate = (args: object) =>
+      program.methods
+        .updateGlobalState({
+          newPlatformWallet: null,
+          newUsdcMint: null,
+          newPlatformFeeBps: null,
+          newBonusPoolFeeBps: null,
+          newVrfProgram: null,
+          newVrfAccount: null,
+          newCrankBounty: null,
+          newMaxCreatorCommissionBps: null,
+          newUpdateDelay: null,
+          newGuardian: null,
+          ...args,
+        })
+        .accounts({
+          globalState: globalStatePda,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    const executeUpdate = () =>
+      program.methods
+        .executePendingUpdate()
+        .accounts({ globalState: globalStatePda })
+        .rpc();
+    const waitForUpdate = async () => {
+      const globalState = await program.account.globalState.fetch(globalStatePda);
+      waitUntil(globalState.pendingUpdate.effectiveAt.toNumber());
+      await executeUpdate();
+    };
+
+    const poolId = await createPool(1, 10, 3600);
+    const before = await program.account.globalState.fetch(globalStatePda);
+
+    // Changes to the delay itself wait for the current delay
+    await queueUpdate({ newUpdateDelay: new anchor.BN(5) });
+    await executeUpdate();
+
+    // A fee change cannot be applied before its delay has passed
+    await queueUpdate({ newPlatformFeeBps: before.platformFeeBps + 10 });
+    try {
+      await executeUpdate();
+      assert.fail("Expected the update to wait for its delay");
+    } catch (err) {
+      assert.include(err.toString(), "UpdateNotReady");
+    }
+    await waitForUpdate();
+
+    const globalState = await program.account.globalState.fetch(globalStatePda);
+    assert.equal(globalState.platformFeeBps, before.platformFeeBps + 10);
+
+    // The pool created before the change keeps the fee it was created with
+    const poolState = await program.account.lotteryPool.fetch(
+      poolAccounts(poolId).lotteryPool
+    );
+    assert.equal(poolState.platformFeeBps, before.platformFeeBps);
+
+    // Restore the fee and the delay for any later tests
+    await queueUpdate({
+      newPlatformFeeBps: before.platformFeeBps,
+      newUpdateDelay: new anchor.BN(0),
+    });
+    await waitForUpdate();
+
+    try {
+      await executeUpdate();
+      assert.fail("Expected executing without a queued update to fail");
+    } catch (err) {
+      assert.include(err.toString(), "NoPendingUpdate");
+    }
+  });
+
+  it("Should keep the payment mint while pools hold funds", async () => {
+    const before = await program.account.globalState.fetch(globalStatePda);
+    const poolId = await createPool(1, 10, 3600);
+
+    // New pools pay out in the mint they were created with and count as active
+    const poolState = await program.account.lotteryPool.fetch(
+      poolAccounts(poolId).lotteryPool
+    );
+    assert.ok(poolState.mint.equals(usdcMint));
+    const globalState = await program.account.globalState.fetch(globalStatePda);
+    assert.equal(
+      globalState.activePools.toNumber(),
+      before.activePools.toNumber() + 1
+    );
+
+    // Ticket money is escrowed in the pool's mint until it is paid out
+    const escrowed = (state) =>
+      state.acceptedMints
+        .find((m) => m.mint.equals(usdcMint))
+        .escrowed.toNumber();
+    await buyTickets(await createParticipant(10_000_000), poolId, 1);
+    const afterBuy = await program.account.globalState.fetch(globalStatePda);
+    assert.equal(
+      escrowed(afterBuy),
+      escrowed(globalState) + poolState.ticketPrice.toNumber()
+    );
+
+    try {
+      await program.methods
+        .updateGlobalState({
+          newPlatformWallet: null,
+          newUsdcMint: Keypair.generate().publicKey,
+          newPlatformFeeBps: null,
+          newBonusPoolFeeBps: null,
+          newVrfProgram: null,
+          newVrfAccount: null,
+          newCrankBounty: null,
+          newMaxCreatorCommissionBps: null,
+          newUpdateDelay: null,
+          newGuardian: null,
+        })
+        .accounts({
+          globalState: globalStatePda,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+      assert.fail("Expected the mint change to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "MintInUse");
+    }
+  });
+
+  it("Should only create pools in accepted mints", async () => {
+    const usdtMint = await createMint(
+      provider.connection,
+      authority,
+      authority.publicKey,
+      null,
+      6
+    );
+    const usdtCreatorAccount = await createAccount(
+      provider.connection,
+      authority,
+      usdtMint,
+      authority.publicKey
+    );
+
+    const updateMintAllowlist = (isAdd: boolean) =>
+      program.methods
+        .updateMintAllowlist(isAdd)
+        .accounts({
+          globalState: globalStatePda,
+          authority: authority.publicKey,
+          mint: usdtMint,
+          bonusPoolTokenAccount: bonusTokenAccountFor(usdtMint),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    const createUsdtPool = async () => {
+      const globalState = await program.account.globalState.fetch(globalStatePda);
+      const poolId = globalState.poolsCount.toNumber();
+      const pool = poolAccounts(poolId);
+
+      await program.methods
+        .initializePool(
+          new anchor.BN(10_000_000),
+          new anchor.BN(1),
+          new anchor.BN(10),
+          new anchor.BN(3600),
+          { equal: { winners: 1 } },
+          false,
+          { rollover: { maxRollovers: 3 } },
+          0
+        )
+        .accounts({
+          globalState: globalStatePda,
+          lotteryPool: pool.lotteryPool,
+          poolTokenAccount: pool.poolTokenAccount,
+          vaultAuthority: pool.vaultAuthority,
+          mint: usdtMint,
+          creatorTokenAccount: usdtCreatorAccount,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+      return poolId;
+    };
+
+    try {
+      await createUsdtPool();
+      assert.fail("Expected a mint that is not accepted to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "MintNotAccepted");
+    }
+
+    // Accepting the mint also creates its bonus pool
+    await updateMintAllowlist(true);
+    const poolId = await createUsdtPool();
+    const poolState = await program.account.lotteryPool.fetch(
+      poolAccounts(poolId).lotteryPool
+    );
+    assert.ok(poolState.mint.equals(usdtMint));
+    const bonusBalance = await provider.connection.getTokenAccountBalance(
+      bonusTokenAccountFor(usdtMint)
+    );
+    assert.equal(Number(bonusBalance.value.amount), 0);
+
+    // Removed mints cannot be used for new pools
+    await updateMintAllowlist(false);
+    try {
+      await createUsdtPool();
+      assert.fail("Expected a removed mint to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "MintNotAccepted");
+    }
+  });
+
+  it("Should credit the prize pool with what a transfer fee mint delivers", async () => {
+    // Token-2022 mint charging a 1% transfer fee
+    const feeMint = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: authority.publicKey,
+          newAccountPubkey: feeMint.publicKey,
+          space: mintLen,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(mintLen),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          feeMint.publicKey,
+          authority.publicKey,
+          authority.publicKey,
+          100,
+          BigInt(1_000_000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+          feeMint.publicKey,
+          6,
+          authority.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ),
+      [authority, feeMint]
+    );
+
+    await program.methods
+      .updateMintAllowlist(true)
+      .accounts({
+        globalState: globalStatePda,
+        authority: authority.publicKey,
+        mint: feeMint.publicKey,
+        bonusPoolTokenAccount: bonusTokenAccountFor(feeMint.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    // Pool selling tickets at 10 tokens
+    const globalState = await program.account.globalState.fetch(globalStatePda);
+    const poolId = globalState.poolsCount.toNumber();
+    const pool = poolAccounts(poolId);
+    await program.methods
+      .initializePool(
+        new anchor.BN(10_000_000),
+        new anchor.BN(1),
+        new anchor.BN(20),
+        new anchor.BN(3600),
+        { equal: { winners: 1 } },
+        false,
+        { rollover: { maxRollovers: 3 } },
+        0
+      )
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        mint: feeMint.publicKey,
+        creatorTokenAccount: await createAccount(
+          provider.connection,
+          authority,
+          feeMint.publicKey,
+          authority.publicKey,
+          undefined,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await addTicketPage(poolId, authority);
+
+    const user = Keypair.generate();
+    await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await new Promise((resolve) => setTimeout(resolve, 500));
+    const tokenAccount = await createAccount(
+      provider.connection,
+      user,
+      feeMint.publicKey,
+      user.publicKey,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await mintTo(
+      provider.connection,
+      authority,
+      feeMint.publicKey,
+      tokenAccount,
+      authority.publicKey,
+      130_000_000,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    // 20 tokens sent, the vault receives 19.8 after the 1% fee
+    await buyTickets({ user, tokenAccount }, poolId, 2, null, TOKEN_2022_PROGRAM_ID);
+
+    const poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.equal(poolState.prizePool.toNumber(), 19_800_000);
+    const userTicket = await program.account.userTicket.fetch(
+      userTicketAccount(user.publicKey, poolId)
+    );
+    assert.equal(userTicket.tickets[0].amountPaid.toNumber(), 9_900_000);
+
+    // 110 tokens sent, the fee is capped at 1 so the vault receives 109. Tickets are
+    // refunded at 9.90909 and the 0.00001 left over is surplus, not prize pool.
+    await buyTickets({ user, tokenAccount }, poolId, 11, null, TOKEN_2022_PROGRAM_ID);
+
+    const updatedPool = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.equal(updatedPool.prizePool.toNumber(), 19_800_000 + 108_999_990);
+    const vault = await provider.connection.getTokenAccountBalance(pool.poolTokenAccount);
+    assert.equal(Number(vault.value.amount) - updatedPool.prizePool.toNumber(), 10);
+  });
+
+  it("Should run a native SOL pool without token accounts", async () => {
+    // Native SOL is accepted as the default pubkey, its bonus pool holds lamports
+    const solBonusPool = bonusTokenAccountFor(PublicKey.default);
+    await program.methods
+      .updateMintAllowlist(true)
+      .accounts({
+        globalState: globalStatePda,
+        authority: authority.publicKey,
+        mint: null,
+        bonusPoolTokenAccount: null,
+        bonusAuthority: solBonusPool,
+        tokenProgram: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    // Pool selling two tickets at 0.1 SOL
+    const globalState = await program.account.globalState.fetch(globalStatePda);
+    const poolId = globalState.poolsCount.toNumber();
+    const pool = poolAccounts(poolId);
+    await program.methods
+      .initializePool(
+        new anchor.BN(100_000_000),
+        new anchor.BN(2),
+        new anchor.BN(2),
+        new anchor.BN(5),
+        { equal: { winners: 1 } },
+        false,
+        { rollover: { maxRollovers: 3 } },
+        0
+      )
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        poolTokenAccount: null,
+        vaultAuthority: pool.vaultAuthority,
+        mint: null,
+        creatorTokenAccount: null,
+        authority: authority.publicKey,
+        tokenProgram: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await addTicketPage(poolId, authority);
+
+    const rentExempt = await provider.connection.getMinimumBalanceForRentExemption(0);
+    assert.equal(await provider.connection.getBalance(pool.vaultAuthority), rentExempt);
+
+    // Tickets are paid straight from the users' wallets
+    const users = [Keypair.generate(), Keypair.generate()];
+    for (const user of [...users, platformWallet]) {
+      await provider.connection.requestAirdrop(
+        user.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+    }
+    await new Promise((resolve) => setTimeout(resolve, 500));
+    for (const user of users) {
+      await program.methods
+        .buyTicket(new anchor.BN(poolId), new anchor.BN(1), null)
+        .accounts({
+          globalState: globalStatePda,
+          lotteryPool: pool.lotteryPool,
+          userTicket: userTicketAccount(user.publicKey, poolId),
+          ticketPage: await currentTicketPage(poolId),
+          userTokenAccount: null,
+          poolTokenAccount: null,
+          vaultAuthority: pool.vaultAuthority,
+          user: user.publicKey,
+          mint: null,
+          tokenProgram: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+    }
+
+    let poolState = await program.account.lotteryPool.fetch(pool.lotteryPool);
+    assert.ok(poolState.mint.equals(PublicKey.default));
+    assert.equal(poolState.prizePool.toNumber(), 200_000_000);
+    assert.equal(
+      await provider.connection.getBalance(pool.vaultAuthority),
+      rentExempt + 200_000_000
+    );
+
+    // Fees are paid out in lamports to the platform wallet and the bonus pool
+    waitUntil(poolState.drawTime.toNumber());
+    await commitDraw(poolId);
+    const platformBefore = await provider.connection.getBalance(platformWallet.publicKey);
+    const bonusBefore = await provider.connection.getBalance(solBonusPool);
+    await program.methods
+      .drawWinner(new anchor.BN(poolId))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        drawHistory: await nextDrawHistory(poolId),
+        poolTokenAccount: null,
+        vaultAuthority: pool.vaultAuthority,
+        platformTokenAccount: null,
+        platformWallet: platformWallet.publicKey,
+        bonusPoolTokenAccount: null,
+        bonusAuthority: solBonusPool,
+        creatorTokenAccount: null,
+        creator: authority.publicKey,
+        crankTokenAccount: null,
+        crank: authority.publicKey,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        mint: null,
+        tokenProgram: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    await resolveWinners(poolId);
+
+    assert.equal(
+      (await provider.connection.getBalance(platformWallet.publicKey)) - platformBefore,
+      (200_000_000 * poolState.platformFeeBps) / 10000
+    );
+    assert.equal(
+      (await provider.connection.getBalance(solBonusPool)) - bonusBefore,
+      (200_000_000 * poolState.bonusPoolFeeBps) / 10000
+    );
+
+    // The winner is paid in lamports, leaving the vault at its rent exempt minimum
+    const drawHistoryPda = await latestDrawHistory(poolId);
+    const drawHistory = await program.account.drawHistory.fetch(drawHistoryPda);
+    const prize = drawHistory.winners[0];
+    const winnerBefore = await provider.connection.getBalance(prize.winner);
+    await program.methods
+      .claimPrize(new anchor.BN(poolId), drawHistory.drawIndex, 0)
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        drawHistory: drawHistoryPda,
+        poolTokenAccount: null,
+        vaultAuthority: pool.vaultAuthority,
+        winner: prize.winner,
+        winnerTokenAccount: null,
+        mint: null,
+        payer: authority.publicKey,
+        tokenProgram: null,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    assert.equal(
+      (await provider.connection.getBalance(prize.winner)) - winnerBefore,
+      prize.amount.toNumber()
+    );
+    assert.equal(await provider.connection.getBalance(pool.vaultAuthority), rentExempt);
+  });
+
+  it("Should pause operations globally and per pool", async () => {
+    const PAUSE_BUY = 1;
+    const guardian = Keypair.generate();
+    await program.methods
+      .updateGlobalState({
+        newPlatformWallet: null,
+        newUsdcMint: null,
+        newPlatformFeeBps: null,
+        newBonusPoolFeeBps: null,
+        newVrfProgram: null,
+        newVrfAccount: null,
+        newCrankBounty: null,
+        newMaxCreatorCommissionBps: null,
+        newUpdateDelay: null,
+        newGuardian: guardian.publicKey,
+      })
+      .accounts({
+        globalState: globalStatePda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const setPause = (signer: Keypair, paused: number) =>
+      program.methods
+        .setPause(paused)
+        .accounts({ globalState: globalStatePda, signer: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    const setPoolPause = (signer: Keypair, poolId: number, paused: boolean) =>
+      program.methods
+        .setPoolPause(new anchor.BN(poolId), paused)
+        .accounts({
+          globalState: globalStatePda,
+          lotteryPool: poolAccounts(poolId).lotteryPool,
+          signer: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+
+    const poolId = await createPool(1, 10, 3600);
+    const participant = await createParticipant(50_000_000);
+
+    // Only the authority and the guardian can pause
+    const stranger = Keypair.generate();
+    try {
+      await setPause(stranger, PAUSE_BUY);
+      assert.fail("Expected a stranger to be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    // The guardian stops ticket sales program wide
+    const [paused] = await eventsOf(await setPause(guardian, PAUSE_BUY));
+    assert.equal(paused.name, "pauseUpdated");
+    assert.equal(paused.data.paused, PAUSE_BUY);
+    assert.isTrue(paused.data.signer.equals(guardian.publicKey));
+    try {
+      await buyTickets(participant, poolId, 1);
+      assert.fail("Expected sales to be paused");
+    } catch (err) {
+      assert.include(err.toString(), "OperationPaused");
+    }
+    await setPause(authority, 0);
+    await buyTickets(participant, poolId, 1);
+
+    // A paused pool rejects sales and resumes in the status it was paused in
+    const [poolPaused] = await eventsOf(await setPoolPause(guardian, poolId, true));
+    assert.equal(poolPaused.name, "poolPauseUpdated");
+    assert.equal(poolPaused.data.poolId.toNumber(), poolId);
+    assert.isTrue(poolPaused.data.paused);
+    let poolState = await program.account.lotteryPool.fetch(poolAccounts(poolId).lotteryPool);
+    assert.deepEqual(poolState.status, { paused: {} });
+    try {
+      await buyTickets(participant, poolId, 1);
+      assert.fail("Expected the pool to be paused");
+    } catch (err) {
+      assert.include(err.toString(), "PoolPaused");
+    }
+    await setPoolPause(authority, poolId, false);
+    poolState = await program.account.lotteryPool.fetch(poolAccounts(poolId).lotteryPool);
+    assert.deepEqual(poolState.status, { active: {} });
+    await buyTickets(participant, poolId, 1);
+  });
+
+  it("Should emit typed events for purchases and cancellations", async () => {
+    const poolId = await createPool(1, 10, 3600);
+    const pool = poolAccounts(poolId);
+    const participant = await createParticipant(50_000_000);
+
+    const [bought] = await eventsOf(await buyTickets(participant, poolId, 3));
+    assert.equal(bought.name, "ticketsBought");
+    assert.equal(bought.data.poolId.toNumber(), poolId);
+    assert.isTrue(bought.data.user.equals(participant.user.publicKey));
+    assert.equal(bought.data.startTicket.toNumber(), 0);
+    assert.equal(bought.data.count.toNumber(), 3);
+    assert.equal(bought.data.amount.toNumber(), 30_000_000);
+
+    const userTicket = userTicketAccount(participant.user.publicKey, poolId);
+    const signature = await program.methods
+      .cancelTicket(new anchor.BN(poolId), new anchor.BN(1))
+      .accounts({
+        globalState: globalStatePda,
+        lotteryPool: pool.lotteryPool,
+        userTicket,
+        userTokenAccount: participant.tokenAccount,
+        poolTokenAccount: pool.poolTokenAccount,
+        vaultAuthority: pool.vaultAuthority,
+        platformTokenAccount: await getAssociatedTokenAddress(usdcMint, platformWallet.publicKey),
+        user: participant.user.publicKey,
+        mint: usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([participant.user])
+      .rpc();
+
+    // The fee breakdown adds up to what the ticket was bought for
+    const [cancelled] = await eventsOf(signature);
+    assert.equal(cancelled.name, "ticketCancelled");
+    assert.equal(cancelled.data.ticketNumber.toNumber(), 1);
+    assert.equal(
+      cancelled.data.refundAmount.toNumber() + cancelled.data.cancellationFee.toNumber(),
+      cancelled.data.amountPaid.toNumber()
+    );
+  });
+
+  it("Should verify a pool vault and sweep donated surplus", async () => {
+    const poolId = await createPool(1, 10, 3600);
+    const pool = poolAccounts(poolId);
+    const participant = await createParticipant(50_000_000);
+    await buyTickets(participant, poolId, 2);
+
+    const verifyPool = () =>
+      program.methods
+        .verifyPool(new anchor.BN(poolId))
+        .accounts({
+          lotteryPool: pool.lotteryPool,
+          poolTokenAccount: pool.poolTokenAccount,
+          vaultAuthority: pool.vaultAuthority,
+        })
+        .remainingAccounts([
+          {
+            pubkey: userTicketAccount(participant.user.publicKey, poolId),
+            isWritable: false,
+            isSigner: false,
+          },
+        ])
+        .view();
+
+    // The vault matches the books after the purchase
+    let report = await verifyPool();
+    assert.equal(report.vaultBalance.toNumber(), 20_000_000);
+    assert.equal(report.expectedBalance.toNumber(), 20_000_000);
+    assert.equal(report.ticketValue.toNumber(), 20_000_000);
+    assert.equal(report.userTicketCount.toNumber(), 2);
+    assert.equal(report.userTicketTotal.toNumber(), 20_000_000);
+    assert.equal(report.surplus.toNumber(), 0);
+
+    // Tokens sent straight to the vault show up as surplus
+    await mintTo(
+      provider.connection,
+      authority,
+      usdcMint,
+      pool.poolTokenAccount,
+      authority.publicKey,
+      1_000
+    );
+    report = await verifyPool();
+    assert.equal(report.surplus.toNumber(), 1_000);
+
+    const platformTokenAccount = await getAssociatedTokenAddress(
+      usdcMint,
+      platformWallet.publicKey
+    );
+    const sweepSurplus = () =>
+      program.methods
+        .sweepSurplus(new anchor.BN(poolId))
+        .accounts({
+          globalState: globalStatePda,
+          lotteryPool: pool.lotteryPool,
+          poolTokenAccount: pool.poolTokenAccount,
+          vaultAuthority: pool.vaultAuthority,
+          platformTokenAccount,
+          authority: authority.publicKey,
+          mint: usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+    const platformBefore = await provider.connection.getTokenAccountBalance(platformTokenAccount);
+    const [swept] = await eventsOf(await sweepSurplus());
+    assert.equal(swept.name, "surplusSwept");
+    assert.equal(swept.data.amount.toNumber(), 1_000);
+    const platformAfter = await provider.connection.getTokenAccountBalance(platformTokenAccount);
+    assert.equal(
+      Number(platformAfter.value.amount) - Number(platformBefore.value.amount),
+      1_000
+    );
+
+    // The prize pool itself can never be swept
+    report = await verifyPool();
+    assert.equal(report.vaultBalance.toNumber(), 20_000_000);
+    try {
+      await sweepSurplus();
+      assert.fail("Expected nothing left to sweep");
+    } catch (err) {
+      assert.include(err.toString(), "NoSurplus");
+    }
   });
 });